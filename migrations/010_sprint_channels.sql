@@ -0,0 +1,62 @@
+ALTER TABLE sprints ADD COLUMN channel channel null;
+
+-- best effort for sprints created before the column existed
+UPDATE sprints SET channel = (announce).channel WHERE announce IS NOT NULL;
+
+CREATE INDEX sprints_guild ON sprints (((channel).guild_id));
+
+-- the views were created with sprints.* which postgres expands at creation
+-- time, so they need to be recreated to pick up the new column
+
+DROP VIEW sprints_current;
+CREATE VIEW sprints_current AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND (
+		sprints.starting_at >= current_timestamp
+		OR sprints.starting_at + sprints.duration >= current_timestamp
+	)
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_ended;
+CREATE VIEW sprints_finished_but_not_ended AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status NOT IN ('Ended', 'Summaried')
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_summaried;
+CREATE VIEW sprints_finished_but_not_summaried AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status != 'Summaried'
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
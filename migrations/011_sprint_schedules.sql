@@ -0,0 +1,26 @@
+-- sprints created by a schedule have no interaction to respond to
+ALTER TABLE sprints ALTER COLUMN interaction_token DROP NOT NULL;
+
+CREATE TABLE sprint_schedules (
+	id uuid primary key default gen_random_uuid(),
+	shortid serial,
+
+	created_at timestamp with time zone not null default current_timestamp,
+	updated_at timestamp with time zone not null default current_timestamp,
+	paused_at timestamp with time zone null,
+
+	member member not null,
+	channel channel not null,
+
+	days text not null,
+	times text not null,
+	duration interval not null,
+
+	last_run_at timestamp with time zone null,
+	next_run_at timestamp with time zone null,
+
+	unique (shortid)
+);
+
+CREATE INDEX sprint_schedules_guild ON sprint_schedules (((channel).guild_id));
+CREATE INDEX sprint_schedules_active ON sprint_schedules ((paused_at is null));
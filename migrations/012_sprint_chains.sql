@@ -0,0 +1,70 @@
+CREATE TABLE sprint_chains (
+	id uuid primary key default gen_random_uuid(),
+
+	created_at timestamp with time zone not null default current_timestamp,
+	updated_at timestamp with time zone not null default current_timestamp,
+	cancelled_at timestamp with time zone null,
+
+	rounds int not null,
+	break_duration interval not null
+);
+
+ALTER TABLE sprints ADD COLUMN chain_id uuid null references sprint_chains (id) on delete set null;
+ALTER TABLE sprints ADD COLUMN chain_round int null;
+
+CREATE INDEX sprints_chain ON sprints (chain_id);
+
+-- recreate the views to pick up the new columns, see 010
+
+DROP VIEW sprints_current;
+CREATE VIEW sprints_current AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND (
+		sprints.starting_at >= current_timestamp
+		OR sprints.starting_at + sprints.duration >= current_timestamp
+	)
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_ended;
+CREATE VIEW sprints_finished_but_not_ended AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status NOT IN ('Ended', 'Summaried')
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_summaried;
+CREATE VIEW sprints_finished_but_not_summaried AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status != 'Summaried'
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
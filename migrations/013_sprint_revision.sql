@@ -0,0 +1,57 @@
+-- bumped whenever a sprint's timing is edited, so timers set up before then can be ignored
+ALTER TABLE sprints ADD COLUMN revision int not null default 0;
+
+-- recreate the views to pick up the new column, see 010
+
+DROP VIEW sprints_current;
+CREATE VIEW sprints_current AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND (
+		sprints.starting_at >= current_timestamp
+		OR sprints.starting_at + sprints.duration >= current_timestamp
+	)
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_ended;
+CREATE VIEW sprints_finished_but_not_ended AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status NOT IN ('Ended', 'Summaried')
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
+
+DROP VIEW sprints_finished_but_not_summaried;
+CREATE VIEW sprints_finished_but_not_summaried AS
+SELECT
+	sprints.*
+FROM
+	sprints
+	LEFT JOIN sprint_participants ON sprints.id = sprint_participants.sprint_id
+WHERE
+	true
+	AND sprints.cancelled_at IS NULL
+	AND sprints.starting_at + sprints.duration <= current_timestamp
+	AND sprints.status != 'Summaried'
+GROUP BY
+	sprints.id
+HAVING
+	count(sprint_participants.*) > 0;
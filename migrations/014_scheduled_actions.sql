@@ -0,0 +1,13 @@
+CREATE TABLE scheduled_actions (
+	id uuid primary key default gen_random_uuid(),
+	key text null,
+
+	created_at timestamp with time zone not null default current_timestamp,
+	due_at timestamp with time zone not null,
+
+	action jsonb not null,
+
+	unique (key)
+);
+
+CREATE INDEX scheduled_actions_due_at ON scheduled_actions (due_at);
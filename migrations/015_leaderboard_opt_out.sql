@@ -0,0 +1 @@
+ALTER TABLE user_preferences ADD COLUMN leaderboard_opt_out boolean not null default false;
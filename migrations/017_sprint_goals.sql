@@ -0,0 +1,2 @@
+-- a participant's personal word goal for the sprint
+ALTER TABLE sprint_participants ADD COLUMN goal int null;
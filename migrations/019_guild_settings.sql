@@ -0,0 +1,4 @@
+CREATE TABLE guild_settings (
+	guild_id bigint primary key,
+	chat_words boolean not null default false
+);
@@ -0,0 +1 @@
+ALTER TABLE user_preferences ADD COLUMN carry_words boolean not null default false;
@@ -0,0 +1,5 @@
+ALTER TABLE guild_settings
+	ADD COLUMN sprint_duration integer not null default 20,
+	ADD COLUMN sprint_when text not null default '15m',
+	ADD COLUMN sprint_warning integer not null default 30,
+	ADD COLUMN choose_weighting boolean not null default true;
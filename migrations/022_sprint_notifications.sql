@@ -0,0 +1,7 @@
+ALTER TABLE guild_settings ADD COLUMN sprinters_role bigint null;
+
+CREATE TABLE sprint_notifications (
+	member member primary key,
+	created_at timestamp with time zone not null default current_timestamp,
+	last_notified_at timestamp with time zone null
+);
@@ -0,0 +1,3 @@
+ALTER TABLE user_preferences
+	ADD COLUMN sprint_reminders boolean not null default true,
+	ADD COLUMN reminder_minutes integer not null default 10;
@@ -0,0 +1,2 @@
+-- minutes after a sprint ends to wait for ending words before summarizing anyway
+ALTER TABLE guild_settings ADD COLUMN summary_grace integer not null default 10;
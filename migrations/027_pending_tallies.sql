@@ -0,0 +1,13 @@
+-- tallies which couldn't be saved on TrackBear yet, retried in the background
+CREATE TABLE pending_tallies (
+	id uuid primary key default gen_random_uuid(),
+
+	created_at timestamp with time zone not null default current_timestamp,
+	updated_at timestamp with time zone not null default current_timestamp,
+
+	member member not null,
+	request jsonb not null,
+
+	attempts integer not null default 0,
+	last_error text null
+);
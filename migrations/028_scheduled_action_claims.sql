@@ -0,0 +1,3 @@
+-- actions are claimed while they run and only removed once they're done,
+-- so a restart in the middle of a batch doesn't lose them
+ALTER TABLE scheduled_actions ADD COLUMN claimed_at timestamp with time zone null;
@@ -0,0 +1,3 @@
+-- whether the starting words were carried from an earlier sprint rather than given,
+-- so they can be corrected once the previous round of a chain gets its ending words
+ALTER TABLE sprint_participants ADD COLUMN words_start_carried boolean not null default false;
@@ -1,30 +1,32 @@
-use futures_util::FutureExt;
+use std::time::Duration;
+
+use chrono::Utc;
 use miette::{Context, IntoDiagnostic, Report, Result};
 use tokio::{
 	signal,
 	sync::mpsc::{self, Receiver},
-	task::{spawn, JoinSet},
+	task::spawn,
+	time::sleep,
 };
 use tracing::{debug, error, info, warn};
-use twilight_gateway::{EventTypeFlags, Shard, ShardId, StreamExt};
-use twilight_model::{
-	application::interaction::{Interaction, InteractionData},
-	gateway::event::Event,
-};
+use twilight_model::application::interaction::{Interaction, InteractionData};
 
-use crate::config::Config;
+use crate::{config::Config, db::scheduled_action::ScheduledAction, error_ext::ErrorExt};
 pub(crate) use context::App;
 
-use self::{action::CommandError, context::Timer};
+use self::action::CommandError;
 
 pub mod action;
 pub mod calc;
 pub mod choose;
 pub mod context;
 pub mod debug;
+pub mod listener;
 pub mod names;
+pub mod preferences;
 pub mod random;
 pub mod related;
+pub mod settings;
 pub mod sprint;
 pub mod timezone;
 pub mod trackbear;
@@ -34,8 +36,8 @@ pub mod words;
 pub async fn start(config: Config) -> Result<()> {
 	let (db, db_task) = config.db.connect().await?;
 
-	let (timer, timings) = mpsc::channel(config.internal.timer_buffer);
-	let app = App::new(config, db, timer);
+	let (ticker, wakes) = mpsc::channel(config.internal.timer_buffer);
+	let app = App::new(config, db, ticker)?;
 
 	let querying = spawn(async {
 		info!("starting db worker");
@@ -52,10 +54,12 @@ pub async fn start(config: Config) -> Result<()> {
 				choose::command()?,
 				debug::command()?,
 				names::command()?,
+				preferences::command()?,
 				timezone::command()?,
 				trackbear::command()?,
 				random::command()?,
 				related::command()?,
+				settings::command()?,
 				sprint::command()?,
 				words::command()?,
 			])
@@ -63,15 +67,22 @@ pub async fn start(config: Config) -> Result<()> {
 			.into_diagnostic()?;
 	}
 
-	let ticking = spawn(ticker(app.clone(), timings));
-	let listening = spawn(listener(app.clone()));
+	let listening = spawn(listener::listen(app.clone()));
 
-	let initing = spawn(async {
-		sprint::load_from_db(app).await?;
-		Ok::<_, Report>(())
+	// catch up before the ticker starts, so that timers replaced here don't also run
+	let initing = spawn({
+		let app = app.clone();
+		async {
+			ScheduledAction::release_claims(app.clone()).await?;
+			sprint::load_from_db(app.clone()).await?;
+			sprint::schedule::load_from_db(app.clone()).await?;
+			words::load_from_db(app).await?;
+			Ok::<_, Report>(())
+		}
 	});
 
 	initing.await.into_diagnostic()??;
+	let ticking = spawn(ticker(app.clone(), wakes));
 	info!("init has finished, good sailing!");
 
 	signal::ctrl_c().await.into_diagnostic()?;
@@ -84,86 +95,47 @@ pub async fn start(config: Config) -> Result<()> {
 	Ok(())
 }
 
-#[tracing::instrument(skip_all)]
-async fn listener(app: App) -> Result<()> {
-	let mut shard = Shard::new(
-		ShardId::ONE,
-		app.config.discord.token.clone(),
-		app.config.discord.intents.to_intent(),
-	);
-
-	info!("created shard");
-
-	loop {
-		let event = match shard.next_event(EventTypeFlags::all()).await {
-			Some(Ok(event)) => event,
-			Some(Err(err)) => {
-				error!(?err, "error receiving event");
-				continue;
-			}
-			None => {
-				info!("shard stream ended");
-				break;
-			}
-		};
-
-		debug!(?event, "spawning off to handle event");
-
-		let app = app.clone();
-		spawn(async move {
-			match event {
-				Event::InteractionCreate(ic) => handle_interaction(app.clone(), &ic.0)
-					.await
-					.wrap_err("event: interaction-create"),
-				_ => Ok(()),
-			}
-			.unwrap_or_else(|err| error!("{err:?}"))
-		});
-	}
-
-	info!("listener exiting");
-	Ok(())
-}
+/// How long the ticker sleeps at most before checking for due timers again.
+const TICKER_IDLE: Duration = Duration::from_secs(60);
 
 #[tracing::instrument(skip_all)]
-async fn ticker(app: App, mut timings: Receiver<Timer>) -> Result<()> {
+async fn ticker(app: App, mut wakes: Receiver<()>) -> Result<()> {
 	info!("initialise ticker");
-	let mut timers = JoinSet::new();
 
 	loop {
-		if timers.is_empty() {
-			debug!("timer queue is empty, watching channel only");
-			if let Some(timer) = timings.recv().await {
-				debug!(?timer, "timer received, enqueueing");
-				timers.spawn(timer.to_sleep().map(|_| timer.payload));
-			} else {
-				debug!("queue is empty and channel is done, ticker exiting");
-				break Ok(());
-			}
-		} else {
-			debug!("watching channel and timers");
-			tokio::select! {
-				Some(timer) = timings.recv() => {
-					debug!(?timer, "timer received, enqueueing");
-					timers.spawn(timer.to_sleep().map(|_| timer.payload));
-				}
-				timer = timers.join_next() => {
-					match timer {
-						None => warn!("ticker timer set is empty, which shouldn't happen on this branch"),
-						Some(Err(err)) => {
-							error!(%err, "timer has failed, this should never happen");
-						}
-						Some(Ok(payload)) => {
-							info!(?payload, "timer has finished, executing");
-							app.do_action(payload).await.unwrap_or_else(|err| error!("{err:?}"));
-						}
-					}
-				}
-				else => {
-					debug!("ticker is finished");
+		let due = ScheduledAction::claim_due(app.clone())
+			.await
+			.log()
+			.unwrap_or_default();
+		for scheduled in due {
+			info!(key=?scheduled.key, due_at=%scheduled.due_at, "timer is due, executing");
+			app.do_action(scheduled.action.clone())
+				.await
+				.unwrap_or_else(|err| error!("{err:?}"));
+			scheduled.done(app.clone()).await.log().ok();
+		}
+
+		let sleep_for = ScheduledAction::next_due_at(app.clone())
+			.await
+			.log()
+			.ok()
+			.flatten()
+			.map_or(TICKER_IDLE, |due_at| {
+				(due_at - Utc::now())
+					.to_std()
+					.unwrap_or_default()
+					.min(TICKER_IDLE)
+			});
+
+		debug!(?sleep_for, "ticker sleeping until next timer");
+		tokio::select! {
+			woken = wakes.recv() => {
+				if woken.is_none() {
+					debug!("ticker channel is done, ticker exiting");
 					break Ok(());
 				}
 			}
+			_ = sleep(sleep_for) => {}
 		}
 	}
 }
@@ -187,6 +159,9 @@ async fn handle_interaction(app: App, interaction: &Interaction) -> Result<()> {
 					"names" => names::on_command(app.clone(), interaction, data)
 						.await
 						.wrap_err("command: names"),
+					"preferences" => preferences::on_command(app.clone(), interaction, data)
+						.await
+						.wrap_err("command: preferences"),
 					"timezone" => timezone::on_command(app.clone(), interaction, data)
 						.await
 						.wrap_err("command: timezone"),
@@ -199,6 +174,9 @@ async fn handle_interaction(app: App, interaction: &Interaction) -> Result<()> {
 					"related" => related::on_command(app.clone(), interaction, data)
 						.await
 						.wrap_err("command: related"),
+					"settings" => settings::on_command(app.clone(), interaction, data)
+						.await
+						.wrap_err("command: settings"),
 					"sprint" => sprint::on_command(app.clone(), interaction, data)
 						.await
 						.wrap_err("command: sprint"),
@@ -238,6 +216,11 @@ async fn handle_interaction(app: App, interaction: &Interaction) -> Result<()> {
 							.await
 							.wrap_err("component: trackbear")
 					}
+					Some(&"words") => {
+						words::on_component(app.clone(), interaction, &subids[1..], data)
+							.await
+							.wrap_err("component: words")
+					}
 					Some(other) => {
 						warn!("unhandled component action: {other:?}");
 						Ok(())
@@ -7,7 +7,7 @@ macro_rules! action {
 			pub use self::$modname::$typename;
 		)*
 
-		#[derive(Debug, Clone)]
+		#[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
 		pub struct Action {
 			pub class: ActionClass,
 		}
@@ -18,6 +18,13 @@ macro_rules! action {
 		}
 
 		impl Action {
+			pub fn name(&self) -> &'static str {
+				use ActionClass::*;
+				match self.class {
+					$($typename(_) => stringify!($typename)),*
+				}
+			}
+
 			pub async fn handle(self, app: super::App) -> ::miette::Result<()> {
 				let args = Args { app };
 
@@ -28,7 +35,7 @@ macro_rules! action {
 			}
 		}
 
-		#[derive(Debug, Clone)]
+		#[derive(Debug, Clone, ::serde::Serialize, ::serde::Deserialize)]
 		pub enum ActionClass {
 			$($typename(Box<$typename>)),*
 		}
@@ -48,19 +55,26 @@ action!(
 	command_error: CommandError,
 	trackbear_login_confirm: TrackbearLoginConfirm,
 	trackbear_login_modal: TrackbearLoginModal,
+	trackbear_retry_tally: TrackbearRetryTally,
 	timezone_modal: TimezoneModal,
 	timezone_show: TimezoneShow,
 	sprint_announce: SprintAnnounce,
 	sprint_cancelled: SprintCancelled,
+	sprint_close_out: SprintCloseOut,
+	sprint_edit_modal: SprintEditModal,
 	sprint_end: SprintEnd,
+	sprint_join_modal: SprintJoinModal,
 	sprint_joined: SprintJoined,
 	sprint_left: SprintLeft,
+	sprint_reminder: SprintReminder,
 	sprint_start: SprintStart,
 	sprint_summary: SprintSummary,
+	sprint_summary_card: SprintSummaryCard,
 	sprint_update: SprintUpdate,
 	sprint_end_warning: SprintEndWarning,
 	sprint_start_warning: SprintStartWarning,
 	sprint_words_end: SprintWordsEnd,
 	sprint_words_start: SprintWordsStart,
-	sprint_save_words: SprintSaveWords
+	sprint_save_words: SprintSaveWords,
+	sprint_schedule_fire: SprintScheduleFire
 );
@@ -1,4 +1,5 @@
 use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::application::interaction::Interaction;
 use twilight_util::builder::embed::EmbedBuilder;
 
@@ -6,7 +7,7 @@ use crate::bot::context::{GenericResponse, GenericResponseData};
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalcResult(GenericResponse);
 
 impl CalcResult {
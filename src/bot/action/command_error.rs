@@ -1,6 +1,7 @@
 use std::iter::repeat;
 
 use miette::{GraphicalReportHandler, GraphicalTheme, IntoDiagnostic, Report, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{Button, ButtonStyle, Component},
@@ -18,7 +19,7 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandError(GenericResponse);
 
 impl CommandError {
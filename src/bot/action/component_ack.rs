@@ -1,4 +1,5 @@
 use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::MessageFlags,
@@ -8,7 +9,7 @@ use twilight_model::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComponentAck {
 	pub id: Id<InteractionMarker>,
 	pub token: String,
@@ -1,28 +1,30 @@
-use chrono::Duration;
 use miette::{miette, Context, Result};
-use tracing::debug;
+use serde::{Deserialize, Serialize};
+use tokio::spawn;
+use tracing::error;
 use twilight_model::{
 	application::interaction::Interaction,
-	channel::message::component::{Button, ButtonStyle, Component},
+	channel::message::component::{
+		Button, ButtonStyle, Component, SelectMenu, SelectMenuOption, SelectMenuType,
+	},
 };
+use uuid::Uuid;
 
 use crate::{
 	bot::{
-		context::{GenericResponse, GenericResponseData, Timer},
-		utils::{
-			action_row,
-			time::{ChronoDateTimeExt, ChronoDurationExt},
-		},
+		context::{GenericResponse, GenericResponseData},
+		sprint::{notify::notify_subscribers, schedule_start_timers},
+		utils::{action_row, time::ChronoDateTimeExt},
 		App,
 	},
 	db::sprint::{Sprint, SprintStatus},
 };
 
-use super::{Action, ActionClass, Args, SprintStart, SprintStartWarning};
+use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintAnnounce {
-	sprint: Box<Sprint>,
+	sprint: Uuid,
 	response: Box<GenericResponse>,
 }
 
@@ -32,8 +34,9 @@ impl SprintAnnounce {
 			return Err(miette!("Bug: went to announce sprint but it was already"));
 		}
 
-		let components = action_row(vec![
-			Component::Button(Button {
+		let mut buttons = Vec::with_capacity(6);
+		if sprint.teams.is_none() && !sprint.private {
+			buttons.push(Component::Button(Button {
 				custom_id: Some(format!("sprint:join:{}", sprint.id)),
 				disabled: false,
 				emoji: None,
@@ -41,7 +44,9 @@ impl SprintAnnounce {
 				style: ButtonStyle::Success,
 				url: None,
 				sku_id: None,
-			}),
+			}));
+		}
+		buttons.extend([
 			Component::Button(Button {
 				custom_id: Some(format!("sprint:cancel:{}", sprint.id)),
 				disabled: false,
@@ -51,34 +56,91 @@ impl SprintAnnounce {
 				url: None,
 				sku_id: None,
 			}),
+			Component::Button(Button {
+				custom_id: Some(format!("sprint:delay:{}", sprint.id)),
+				disabled: false,
+				emoji: None,
+				label: Some("+5m".to_string()),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}),
+			Component::Button(Button {
+				custom_id: Some(format!("sprint:edit:{}", sprint.id)),
+				disabled: false,
+				emoji: None,
+				label: Some("Edit".to_string()),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}),
 		]);
+		if !sprint.private {
+			buttons.push(Component::Button(Button {
+				custom_id: Some(format!("sprint:host-tools:{}", sprint.id)),
+				disabled: false,
+				emoji: None,
+				label: Some("Host".to_string()),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}));
+		}
+		if let Some(chain_id) = sprint.chain_id {
+			buttons.push(Component::Button(Button {
+				custom_id: Some(format!("sprint:cancel-chain:{chain_id}")),
+				disabled: false,
+				emoji: None,
+				label: Some("Cancel chain".to_string()),
+				style: ButtonStyle::Danger,
+				url: None,
+				sku_id: None,
+			}));
+		}
+		let mut components = Vec::with_capacity(3);
+		if let Some(teams) = &sprint.teams {
+			// a select menu needs a row to itself
+			components.extend(action_row(vec![Component::SelectMenu(SelectMenu {
+				channel_types: None,
+				custom_id: format!("sprint:join-team:{}", sprint.id),
+				default_values: None,
+				disabled: false,
+				kind: SelectMenuType::Text,
+				max_values: Some(1),
+				min_values: Some(1),
+				options: Some(
+					teams
+						.iter()
+						.enumerate()
+						.map(|(index, team)| SelectMenuOption {
+							default: false,
+							description: None,
+							emoji: None,
+							label: team.clone(),
+							value: index.to_string(),
+						})
+						.collect(),
+				),
+				placeholder: Some("Join a team".to_string()),
+			})]));
+		}
+		// rows can't have more than five buttons
+		for row in buttons.chunks(5) {
+			components.extend(action_row(row.to_vec()));
+		}
 
 		sprint
 			.update_status(app.clone(), SprintStatus::Announced)
 			.await?;
 
-		let warning_in = sprint.warning_in();
-		if !warning_in.is_zero() {
-			debug!(?warning_in, "set up sprint warn timer");
-			app.send_timer(Timer::new_after(
-				// UNWRAP: warning_in uses saturating_sub, will never be negative
-				warning_in.to_std().unwrap(),
-				SprintStartWarning::new(sprint),
-			)?)
-			.await?;
-		}
+		schedule_start_timers(app.clone(), sprint).await?;
 
-		let starting_in = sprint.starting_in();
-		debug!(?starting_in, "set up sprint start timer");
-		app.send_timer(Timer::new_after(
-			starting_in.positive_or(Duration::zero()).to_std().unwrap(),
-			SprintStart::new(sprint),
-		)?)
-		.await?;
+		let content = sprint.announce_text(app).await?;
 
 		Ok(GenericResponseData {
-			content: Some(sprint.status_text(app, true).await?),
+			content: Some(content),
 			components,
+			ephemeral: sprint.private,
 			..Default::default()
 		})
 	}
@@ -90,7 +152,7 @@ impl SprintAnnounce {
 				GenericResponse::from_interaction(interaction, Self::prepare(app, &sprint).await?)
 					.with_age(sprint.created_at.elapsed()?),
 			),
-			sprint: Box::new(sprint),
+			sprint: sprint.id,
 		}))
 		.into())
 	}
@@ -102,16 +164,28 @@ impl SprintAnnounce {
 				&sprint,
 				Self::prepare(app, &sprint).await?,
 			)),
-			sprint: Box::new(sprint),
+			sprint: sprint.id,
 		}))
 		.into())
 	}
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		let message = app.send_response(*self.response).await?;
-		self.sprint
-			.set_announce(app, (&message).try_into().wrap_err("convert message")?)
-			.await?;
+		let sprint = Sprint::get(app.clone(), self.sprint).await?;
+		if sprint.private {
+			// ephemeral messages can't be linked to nor edited later
+			return Ok(());
+		}
+
+		let announce = (&message).try_into().wrap_err("convert message")?;
+		sprint.set_announce(app.clone(), announce).await?;
+
+		// DMing a lot of subscribers takes a while, so don't hold up the other timers
+		spawn(async move {
+			notify_subscribers(app, &sprint, announce)
+				.await
+				.unwrap_or_else(|err| error!("{err:?}"))
+		});
 		Ok(())
 	}
 }
@@ -1,4 +1,5 @@
 use miette::Result;
+use serde::{Deserialize, Serialize};
 use twilight_mention::Mention;
 use twilight_model::{application::interaction::Interaction, user::User};
 
@@ -6,13 +7,14 @@ use crate::{
 	bot::{
 		context::{GenericResponse, GenericResponseData},
 		utils::time::ChronoDateTimeExt,
+		App,
 	},
-	db::sprint::Sprint,
+	db::{sprint::Sprint, sprint_chain::SprintChain},
 };
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintCancelled(GenericResponse);
 
 impl SprintCancelled {
@@ -27,6 +29,7 @@ impl SprintCancelled {
 						sprint.shortid,
 						user.id.mention()
 					)),
+					ephemeral: sprint.private,
 					..Default::default()
 				},
 			)
@@ -35,6 +38,31 @@ impl SprintCancelled {
 		.into())
 	}
 
+	#[tracing::instrument(name = "SprintCancelled::new_chain", skip(app, interaction))]
+	pub async fn new_chain(
+		app: App,
+		interaction: &Interaction,
+		chain: &SprintChain,
+		user: &User,
+	) -> Result<Action> {
+		let mut content = format!("❌ Sprint chain was cancelled by {}", user.id.mention());
+		if let Some(totals) = chain.summary_text(app).await? {
+			content.push_str("\n\n");
+			content.push_str(&totals);
+		}
+
+		Ok(
+			ActionClass::SprintCancelled(Box::new(Self(GenericResponse::from_interaction(
+				interaction,
+				GenericResponseData {
+					content: Some(content),
+					..Default::default()
+				},
+			))))
+			.into(),
+		)
+	}
+
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		app.send_response(self.0).await.map(drop)
 	}
@@ -0,0 +1,39 @@
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::db::sprint::{Sprint, SprintStatus};
+
+use super::{Action, ActionClass, Args, SprintSummary};
+
+/// Summarize a sprint once its grace period is over, even if not everyone gave their ending words.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintCloseOut(Uuid, i32);
+
+impl SprintCloseOut {
+	#[tracing::instrument(name = "SprintCloseOut")]
+	pub fn new(sprint: &Sprint) -> Action {
+		ActionClass::SprintCloseOut(Box::new(Self(sprint.id, sprint.revision))).into()
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		let sprint = Sprint::get(app.clone(), self.0).await?;
+		if sprint.is_cancelled() {
+			debug!("sprint was cancelled, not closing out");
+			return Ok(());
+		}
+		if sprint.revision != self.1 {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
+		if sprint.status >= SprintStatus::Summaried {
+			debug!("sprint was already summaried, nothing to close out");
+			return Ok(());
+		}
+
+		debug!("grace period is over, summarizing sprint");
+		app.do_action(SprintSummary::new_from_db(app.clone(), sprint).await?)
+			.await
+	}
+}
@@ -0,0 +1,86 @@
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+	application::interaction::Interaction,
+	channel::message::component::{ActionRow, Component, TextInput, TextInputStyle},
+	http::interaction::{InteractionResponse, InteractionResponseType},
+	id::{marker::InteractionMarker, Id},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+use uuid::Uuid;
+
+use crate::db::sprint::{Sprint, SprintStatus};
+
+use super::{Action, ActionClass, Args};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintEditModal {
+	pub id: Id<InteractionMarker>,
+	pub token: String,
+	pub sprint: Uuid,
+}
+
+impl SprintEditModal {
+	#[tracing::instrument(name = "SprintEditModal", skip(interaction))]
+	pub fn new(interaction: &Interaction, sprint: &Sprint) -> Action {
+		ActionClass::SprintEditModal(Box::new(Self {
+			id: interaction.id,
+			token: interaction.token.clone(),
+			sprint: sprint.id,
+		}))
+		.into()
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		let sprint = Sprint::get(app.clone(), self.sprint).await?;
+		let Sprint { id, shortid, .. } = sprint;
+
+		let mut components = Vec::with_capacity(2);
+		if sprint.status < SprintStatus::Started {
+			components.push(Component::ActionRow(ActionRow {
+				components: vec![Component::TextInput(TextInput {
+					custom_id: "when".into(),
+					label: "New start time (leave empty to keep)".into(),
+					max_length: Some(20),
+					min_length: None,
+					placeholder: Some("08:30, or 15m from now".into()),
+					required: Some(false),
+					style: TextInputStyle::Short,
+					value: None,
+				})],
+			}));
+		}
+		components.push(Component::ActionRow(ActionRow {
+			components: vec![Component::TextInput(TextInput {
+				custom_id: "duration".into(),
+				label: "Duration in minutes".into(),
+				max_length: Some(4),
+				min_length: Some(1),
+				placeholder: None,
+				required: Some(true),
+				style: TextInputStyle::Short,
+				value: Some(sprint.duration().num_minutes().to_string()),
+			})],
+		}));
+
+		app.interaction_client()
+			.create_response(
+				self.id,
+				&self.token,
+				&InteractionResponse {
+					kind: InteractionResponseType::Modal,
+					data: Some(
+						InteractionResponseDataBuilder::new()
+							.custom_id(format!("sprint:edit:{id}"))
+							.title(format!("Edit sprint {shortid}"))
+							.components(components)
+							.build(),
+					),
+				},
+			)
+			.await
+			.into_diagnostic()?;
+
+		Ok(())
+	}
+}
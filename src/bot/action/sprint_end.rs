@@ -1,5 +1,6 @@
 use itertools::Itertools;
 use miette::{miette, Result};
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 use twilight_mention::Mention;
 use twilight_model::channel::message::component::{Button, ButtonStyle, Component};
@@ -7,21 +8,24 @@ use uuid::Uuid;
 
 use crate::{
 	bot::{
-		context::{GenericResponse, GenericResponseData},
+		context::{GenericResponse, GenericResponseData, Timer},
 		utils::action_row,
 	},
-	db::sprint::{Sprint, SprintStatus},
+	db::{
+		sprint::{Sprint, SprintStatus},
+		sprint_chain::SprintChain,
+	},
 };
 
-use super::{Action, ActionClass, Args};
+use super::{Action, ActionClass, Args, SprintAnnounce, SprintCloseOut};
 
-#[derive(Debug, Clone)]
-pub struct SprintEnd(Uuid);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintEnd(Uuid, i32);
 
 impl SprintEnd {
 	#[tracing::instrument(name = "SprintEnd")]
 	pub fn new(sprint: &Sprint) -> Action {
-		ActionClass::SprintEnd(Box::new(Self(sprint.id))).into()
+		ActionClass::SprintEnd(Box::new(Self(sprint.id, sprint.revision))).into()
 	}
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
@@ -30,6 +34,10 @@ impl SprintEnd {
 			debug!("sprint was cancelled, not ending");
 			return Ok(());
 		}
+		if sprint.revision != self.1 {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
 		if sprint.status >= SprintStatus::Ended {
 			return Err(miette!("Bug: went to end sprint but it was already"));
 		}
@@ -49,15 +57,26 @@ impl SprintEnd {
 			.update_status(app.clone(), SprintStatus::Ended)
 			.await?;
 
-		let components = action_row(vec![Component::Button(Button {
-			custom_id: Some(format!("sprint:end-words:{id}")),
-			disabled: false,
-			emoji: None,
-			label: Some("Ending words".to_string()),
-			style: ButtonStyle::Secondary,
-			url: None,
-			sku_id: None,
-		})]);
+		let components = action_row(vec![
+			Component::Button(Button {
+				custom_id: Some(format!("sprint:end-words:{id}")),
+				disabled: false,
+				emoji: None,
+				label: Some(format!("Ending {}", sprint.measure.plural())),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}),
+			Component::Button(Button {
+				custom_id: Some(format!("sprint:summarize:{id}")),
+				disabled: false,
+				emoji: None,
+				label: Some("Summarize now".to_string()),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}),
+		]);
 
 		app.send_response(GenericResponse::from_sprint(
 			&sprint,
@@ -67,7 +86,24 @@ impl SprintEnd {
 				..Default::default()
 			},
 		))
-		.await
-		.map(drop)
+		.await?;
+
+		// don't wait forever on participants who wandered off
+		let grace = sprint.guild_settings(app.clone()).await?.summary_grace();
+		app.send_timer(
+			Timer::new_at(sprint.ending_at() + grace, SprintCloseOut::new(&sprint))
+				.with_key(sprint.timer_key("close-out")),
+		)
+		.await?;
+
+		if let Some(chain_id) = sprint.chain_id {
+			let chain = SprintChain::get(app.clone(), chain_id).await?;
+			if let Some(next) = chain.next_round(app.clone(), &sprint).await? {
+				app.do_action(SprintAnnounce::new_from_db(app.clone(), next).await?)
+					.await?;
+			}
+		}
+
+		Ok(())
 	}
 }
@@ -1,4 +1,6 @@
 use miette::{miette, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 use uuid::Uuid;
 
 use crate::{
@@ -11,17 +13,21 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
-pub struct SprintEndWarning(Uuid);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintEndWarning(Uuid, i32);
 
 impl SprintEndWarning {
 	#[tracing::instrument(name = "SprintEndWarning")]
 	pub fn new(sprint: &Sprint) -> Action {
-		ActionClass::SprintEndWarning(Box::new(Self(sprint.id))).into()
+		ActionClass::SprintEndWarning(Box::new(Self(sprint.id, sprint.revision))).into()
 	}
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		let sprint = Sprint::get_current(app.clone(), self.0).await?;
+		if sprint.revision != self.1 {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
 		if sprint.status >= SprintStatus::Ended {
 			return Err(miette!("Bug: went to warn sprint but it was already ended"));
 		}
@@ -0,0 +1,79 @@
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+	application::interaction::Interaction,
+	channel::message::component::{Component, TextInput, TextInputStyle},
+	http::interaction::{InteractionResponse, InteractionResponseType},
+	id::{marker::InteractionMarker, Id},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{bot::utils::action_row, db::sprint::Sprint};
+
+use super::{Action, ActionClass, Args};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintJoinModal {
+	pub id: Id<InteractionMarker>,
+	pub token: String,
+	pub custom_id: String,
+	pub title: String,
+	pub goal_label: String,
+}
+
+impl SprintJoinModal {
+	#[tracing::instrument(name = "SprintJoinModal", skip(interaction))]
+	pub fn new(interaction: &Interaction, sprint: &Sprint, team: Option<usize>) -> Action {
+		let Sprint { id, shortid, .. } = sprint;
+		let team_name = team.and_then(|index| sprint.teams.as_ref()?.get(index));
+		ActionClass::SprintJoinModal(Box::new(Self {
+			id: interaction.id,
+			token: interaction.token.clone(),
+			custom_id: match team {
+				Some(index) => format!("sprint:join:{id}:{index}"),
+				None => format!("sprint:join:{id}"),
+			},
+			title: match team_name {
+				Some(team) => format!("Join team {team} in sprint {shortid}"),
+				None => format!("Join sprint {shortid}"),
+			},
+			goal_label: format!(
+				"Goal in {} for this sprint (optional)",
+				sprint.measure.plural()
+			),
+		}))
+		.into()
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		app.interaction_client()
+			.create_response(
+				self.id,
+				&self.token,
+				&InteractionResponse {
+					kind: InteractionResponseType::Modal,
+					data: Some(
+						InteractionResponseDataBuilder::new()
+							.custom_id(self.custom_id)
+							// modal titles can't be longer than that
+							.title(self.title.chars().take(45).collect::<String>())
+							.components(action_row(vec![Component::TextInput(TextInput {
+								custom_id: "goal".into(),
+								label: self.goal_label,
+								max_length: Some(20),
+								min_length: None,
+								placeholder: Some("500".into()),
+								required: Some(false),
+								style: TextInputStyle::Short,
+								value: None,
+							})]))
+							.build(),
+					),
+				},
+			)
+			.await
+			.into_diagnostic()?;
+
+		Ok(())
+	}
+}
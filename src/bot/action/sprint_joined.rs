@@ -1,4 +1,5 @@
 use miette::Result;
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{Button, ButtonStyle, Component},
@@ -14,25 +15,29 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintJoined(GenericResponse);
 
 impl SprintJoined {
 	#[tracing::instrument(name = "SprintJoined", skip(interaction))]
-	pub fn new(interaction: &Interaction, sprint: &Sprint) -> Result<Action> {
+	pub fn new(interaction: &Interaction, sprint: &Sprint, team: Option<&str>) -> Result<Action> {
 		let Sprint { id, shortid, .. } = sprint;
+		let content = match team {
+			Some(team) => format!("You're in team {team} for sprint `{shortid}`!"),
+			None => format!("You've joined sprint `{shortid}`!"),
+		};
 		Ok(ActionClass::SprintJoined(Box::new(Self(
 			GenericResponse::from_interaction(
 				interaction,
 				GenericResponseData {
 					ephemeral: true,
-					content: Some(format!("You've joined sprint `{shortid}`!")),
+					content: Some(content),
 					components: action_row(vec![
 						Component::Button(Button {
 							custom_id: Some(format!("sprint:start-words:{id}")),
 							disabled: false,
 							emoji: None,
-							label: Some("Record starting words".to_string()),
+							label: Some(format!("Record starting {}", sprint.measure.plural())),
 							style: ButtonStyle::Primary,
 							url: None,
 							sku_id: None,
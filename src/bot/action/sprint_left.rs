@@ -1,4 +1,5 @@
 use miette::Result;
+use serde::{Deserialize, Serialize};
 use twilight_model::application::interaction::Interaction;
 
 use crate::{
@@ -11,7 +12,7 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintLeft(GenericResponse);
 
 impl SprintLeft {
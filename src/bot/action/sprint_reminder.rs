@@ -0,0 +1,114 @@
+use std::fmt;
+
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+	bot::utils::time::ChronoDateTimeExt,
+	db::{
+		member::Member,
+		sprint::{Sprint, SprintStatus},
+		user_preference::UserPreference,
+	},
+};
+
+use super::{Action, ActionClass, Args};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ReminderKind {
+	/// A little before the sprint starts.
+	Start,
+	/// After the sprint ends, if the member didn't give their ending words.
+	End,
+}
+
+impl fmt::Display for ReminderKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Start => write!(f, "start"),
+			Self::End => write!(f, "end"),
+		}
+	}
+}
+
+/// A DM to one participant of a sprint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintReminder {
+	pub sprint: Uuid,
+	pub revision: i32,
+	pub member: Member,
+	pub kind: ReminderKind,
+}
+
+impl SprintReminder {
+	#[tracing::instrument(name = "SprintReminder")]
+	pub fn new(sprint: &Sprint, member: Member, kind: ReminderKind) -> Action {
+		ActionClass::SprintReminder(Box::new(Self {
+			sprint: sprint.id,
+			revision: sprint.revision,
+			member,
+			kind,
+		}))
+		.into()
+	}
+
+	pub fn timer_key(sprint: &Sprint, member: Member, kind: ReminderKind) -> String {
+		sprint.timer_key(&format!("reminder-{kind}:{}", member.user_id))
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		let sprint = Sprint::get(app.clone(), self.sprint).await?;
+		if sprint.is_cancelled() {
+			debug!("sprint was cancelled, not reminding");
+			return Ok(());
+		}
+		if sprint.revision != self.revision {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
+
+		let Ok(participant) = sprint.participant(app.clone(), self.member).await else {
+			debug!("member left the sprint, not reminding");
+			return Ok(());
+		};
+
+		// they may have turned reminders off since joining
+		if !UserPreference::get_or_create(app.clone(), self.member)
+			.await?
+			.sprint_reminders
+		{
+			return Ok(());
+		}
+
+		let shortid = sprint.shortid;
+		let link = sprint
+			.announce
+			.map_or(String::new(), |msg| format!(" {}", msg.link()));
+		let content = match self.kind {
+			ReminderKind::Start => {
+				if sprint.status >= SprintStatus::Started {
+					return Ok(());
+				}
+
+				format!(
+					"⏰ Sprint `{shortid}` starts {}, get ready!{link}",
+					sprint.starting_at.discord_format('R')
+				)
+			}
+			ReminderKind::End => {
+				if participant.words_end.is_some() || sprint.status >= SprintStatus::Summaried {
+					return Ok(());
+				}
+
+				format!(
+					"⌛ Sprint `{shortid}` is over! How many {} did you end with?{link}",
+					sprint.measure.plural()
+				)
+			}
+		};
+
+		app.send_dm(self.member, &content).await.map(drop)
+	}
+}
@@ -1,4 +1,5 @@
 use miette::Result;
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{Button, ButtonStyle, Component},
@@ -11,12 +12,15 @@ use crate::{
 		App,
 	},
 	db::{member::Member, project::Project, sprint::Sprint, trackbear_login::TrackbearLogin},
-	trackbear::Project as TrackbearProject,
+	trackbear::{
+		client::{is_transient, ApiError},
+		Project as TrackbearProject,
+	},
 };
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintSaveWords(GenericResponse);
 
 impl SprintSaveWords {
@@ -39,14 +43,23 @@ impl SprintSaveWords {
 			return Ok(None);
 		}
 
-		// Try to get a client - returns None if no API key, empty key, or invalid key
-		let Some(client) = TrackbearLogin::client_for_member(app.clone(), member).await? else {
+		// Not checking the key, so words can still be offered (and queued) while TrackBear is down
+		let Some(client) = login.client_unchecked(app.clone()) else {
 			return Ok(None);
 		};
 
 		// Fetch project title from TrackBear API
-		let trackbear_project = TrackbearProject::fetch(&client, project.trackbear_id).await?;
-		let title = trackbear_project.title();
+		let title = match TrackbearProject::fetch(&client, project.trackbear_id).await {
+			Ok(trackbear_project) => trackbear_project.title().to_string(),
+			// it's only for show, so a timeout is fine here too
+			Err(err)
+				if is_transient(&err)
+					|| matches!(err.downcast_ref(), Some(ApiError::Unreachable(_))) =>
+			{
+				"your project".to_string()
+			}
+			Err(err) => return Err(err),
+		};
 
 		let participant = sprint.participant(app.clone(), member).await?;
 		let Some(diff) = participant.words_written() else {
@@ -62,7 +75,10 @@ impl SprintSaveWords {
 				interaction,
 				GenericResponseData {
 					ephemeral: true,
-					content: Some(format!("Save {diff:+} words to «{title}» on TrackBear?")),
+					content: Some(format!(
+						"Save {diff:+} {plural} to «{title}» on TrackBear?",
+						plural = sprint.measure.plural()
+					)),
 					components: action_row(vec![
 						Component::Button(Button {
 							custom_id: Some(format!(
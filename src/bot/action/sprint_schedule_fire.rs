@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+	bot::sprint::schedule::schedule_next,
+	db::{sprint::Sprint, sprint_schedule::SprintSchedule},
+};
+
+use super::{Action, ActionClass, Args, SprintAnnounce};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintScheduleFire {
+	schedule: Uuid,
+	starting_at: DateTime<Utc>,
+}
+
+impl SprintScheduleFire {
+	#[tracing::instrument(name = "SprintScheduleFire")]
+	pub fn new(schedule: &SprintSchedule, starting_at: DateTime<Utc>) -> Action {
+		ActionClass::SprintScheduleFire(Box::new(Self {
+			schedule: schedule.id,
+			starting_at,
+		}))
+		.into()
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		let Some(schedule) = SprintSchedule::get(app.clone(), self.schedule).await? else {
+			debug!(?self.schedule, "sprint schedule was deleted, not firing");
+			return Ok(());
+		};
+
+		if !schedule.claim_run(app.clone(), self.starting_at).await? {
+			debug!(?self.schedule, "sprint schedule run is stale, not firing");
+			return Ok(());
+		}
+
+		// set up the next run first so a failure here doesn't stop the series
+		schedule_next(app.clone(), &schedule, self.starting_at).await?;
+
+		let sprint = Sprint::create(
+			app.clone(),
+			self.starting_at,
+			schedule.duration(),
+			None,
+			schedule.channel,
+			Some(schedule.member),
+		)
+		.await?;
+
+		app.do_action(SprintAnnounce::new_from_db(app.clone(), sprint).await?)
+			.await
+	}
+}
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use miette::{miette, Result};
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 use twilight_mention::Mention;
 use twilight_model::channel::message::component::{Button, ButtonStyle, Component};
@@ -8,25 +8,30 @@ use uuid::Uuid;
 
 use crate::{
 	bot::{
-		context::{GenericResponse, GenericResponseData, Timer},
+		context::{GenericResponse, GenericResponseData},
+		sprint::schedule_end_timers,
 		utils::{action_row, time::ChronoDateTimeExt},
 	},
 	db::sprint::{Sprint, SprintStatus},
 };
 
-use super::{Action, ActionClass, Args, SprintEnd, SprintEndWarning};
+use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
-pub struct SprintStart(Uuid);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintStart(Uuid, i32);
 
 impl SprintStart {
 	#[tracing::instrument(name = "SprintStart")]
 	pub fn new(sprint: &Sprint) -> Action {
-		ActionClass::SprintStart(Box::new(Self(sprint.id))).into()
+		ActionClass::SprintStart(Box::new(Self(sprint.id, sprint.revision))).into()
 	}
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		let sprint = Sprint::get_current(app.clone(), self.0).await?;
+		if sprint.revision != self.1 {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
 		if sprint.status >= SprintStatus::Started {
 			return Err(miette!("Bug: went to start sprint but it was already"));
 		}
@@ -51,45 +56,35 @@ impl SprintStart {
 			.update_status(app.clone(), SprintStatus::Started)
 			.await?;
 
-		if let Ok(ending_in) = sprint.ending_in().to_std() {
-			debug!("set up sprint end timer");
-			app.send_timer(Timer::new_after(ending_in, SprintEnd::new(&sprint))?)
-				.await?;
+		schedule_end_timers(app.clone(), &sprint).await?;
 
-			debug!("set up sprint end warning timer");
-			app.send_timer(Timer::new_after(
-				ending_in.saturating_sub(Duration::from_secs(30)),
-				SprintEndWarning::new(&sprint),
-			)?)
-			.await?;
-		} else {
-			return Err(miette!("sprint ended before it began???"));
+		let mut buttons = Vec::with_capacity(2);
+		if !sprint.private {
+			buttons.push(Component::Button(Button {
+				custom_id: Some(format!("sprint:join:{id}")),
+				disabled: false,
+				emoji: None,
+				label: Some("Join late".to_string()),
+				style: ButtonStyle::Secondary,
+				url: None,
+				sku_id: None,
+			}));
 		}
+		buttons.push(Component::Button(Button {
+			custom_id: Some(format!("sprint:start-words:{id}")),
+			disabled: false,
+			emoji: None,
+			label: Some(format!("Starting {}", sprint.measure.plural())),
+			style: ButtonStyle::Secondary,
+			url: None,
+			sku_id: None,
+		}));
 
 		app.send_response(GenericResponse::from_sprint(
 			&sprint,
 			GenericResponseData {
 				content: Some(content),
-				components: action_row(vec![
-					Component::Button(Button {
-						custom_id: Some(format!("sprint:join:{id}")),
-						disabled: false,
-						emoji: None,
-						label: Some("Join late".to_string()),
-						style: ButtonStyle::Secondary,
-						url: None,
-						sku_id: None,
-					}),
-					Component::Button(Button {
-						custom_id: Some(format!("sprint:start-words:{id}")),
-						disabled: false,
-						emoji: None,
-						label: Some("Starting words".to_string()),
-						style: ButtonStyle::Secondary,
-						url: None,
-						sku_id: None,
-					}),
-				]),
+				components: action_row(buttons),
 				..Default::default()
 			},
 		))
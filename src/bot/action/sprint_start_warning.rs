@@ -1,6 +1,8 @@
 use chrono::Duration;
 use itertools::Itertools;
 use miette::{miette, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 use twilight_mention::Mention;
 use twilight_model::channel::message::component::{Button, ButtonStyle, Component};
 use uuid::Uuid;
@@ -15,17 +17,21 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
-pub struct SprintStartWarning(Uuid);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintStartWarning(Uuid, i32);
 
 impl SprintStartWarning {
 	#[tracing::instrument(name = "SprintStartWarning")]
 	pub fn new(sprint: &Sprint) -> Action {
-		ActionClass::SprintStartWarning(Box::new(Self(sprint.id))).into()
+		ActionClass::SprintStartWarning(Box::new(Self(sprint.id, sprint.revision))).into()
 	}
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		let sprint = Sprint::get_current(app.clone(), self.0).await?;
+		if sprint.revision != self.1 {
+			debug!("sprint was edited since this timer was set, skipping");
+			return Ok(());
+		}
 		if sprint.status >= SprintStatus::Started {
 			return Err(miette!(
 				"Bug: went to warn sprint but it was already started"
@@ -40,20 +46,29 @@ impl SprintStartWarning {
 			sprint.starting_at.discord_format('R')
 		};
 
-		let participant_list = sprint
+		let mut participant_list = sprint
 			.participants(app.clone())
 			.await?
 			.iter()
 			.map(|p| p.mention().to_string())
 			.join(", ");
+		if let Some(role) = sprint
+			.guild_settings(app.clone())
+			.await?
+			.sprinters_mention()
+			.filter(|_| !sprint.private)
+		{
+			participant_list = format!("{participant_list} {role}");
+		}
 
 		let content = format!(
 			"⏱️ Sprint `{shortid}` is starting {starting_in} for {duration}! // {participant_list}"
 		);
 		// TODO: ding
 
-		let components = action_row(vec![
-			Component::Button(Button {
+		let mut buttons = Vec::with_capacity(2);
+		if !sprint.private {
+			buttons.push(Component::Button(Button {
 				custom_id: Some(format!("sprint:join:{id}")),
 				disabled: false,
 				emoji: None,
@@ -61,17 +76,18 @@ impl SprintStartWarning {
 				style: ButtonStyle::Secondary,
 				url: None,
 				sku_id: None,
-			}),
-			Component::Button(Button {
-				custom_id: Some(format!("sprint:start-words:{id}")),
-				disabled: false,
-				emoji: None,
-				label: Some("Starting words".to_string()),
-				style: ButtonStyle::Primary,
-				url: None,
-				sku_id: None,
-			}),
-		]);
+			}));
+		}
+		buttons.push(Component::Button(Button {
+			custom_id: Some(format!("sprint:start-words:{id}")),
+			disabled: false,
+			emoji: None,
+			label: Some(format!("Starting {}", sprint.measure.plural())),
+			style: ButtonStyle::Primary,
+			url: None,
+			sku_id: None,
+		}));
+		let components = action_row(buttons);
 
 		app.send_response(GenericResponse::from_sprint(
 			&sprint,
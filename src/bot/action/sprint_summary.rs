@@ -1,4 +1,5 @@
 use miette::Result;
+use serde::{Deserialize, Serialize};
 use tracing::debug;
 use twilight_model::application::interaction::Interaction;
 
@@ -8,12 +9,15 @@ use crate::{
 		utils::time::ChronoDateTimeExt,
 		App,
 	},
-	db::sprint::{Sprint, SprintStatus},
+	db::{
+		sprint::{Sprint, SprintStatus},
+		sprint_chain::SprintChain,
+	},
 };
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintSummary(GenericResponse);
 
 impl SprintSummary {
@@ -21,13 +25,14 @@ impl SprintSummary {
 	pub async fn new(app: App, interaction: &Interaction, sprint: Sprint) -> Result<Action> {
 		update_status(&sprint, app.clone()).await?;
 
-		let summary = sprint.summary_text(app).await?;
+		let summary = summary_text(&sprint, app).await?;
 
 		Ok(ActionClass::SprintSummary(Box::new(Self(
 			GenericResponse::from_interaction(
 				interaction,
 				GenericResponseData {
 					content: Some(summary),
+					ephemeral: sprint.private,
 					..Default::default()
 				},
 			)
@@ -40,7 +45,7 @@ impl SprintSummary {
 	pub async fn new_from_db(app: App, sprint: Sprint) -> Result<Action> {
 		update_status(&sprint, app.clone()).await?;
 
-		let summary = sprint.summary_text(app).await?;
+		let summary = summary_text(&sprint, app).await?;
 		debug!("got summary, let's post it");
 
 		Ok(
@@ -61,7 +66,7 @@ impl SprintSummary {
 	}
 }
 
-async fn update_status(sprint: &Sprint, app: App) -> Result<()> {
+pub(super) async fn update_status(sprint: &Sprint, app: App) -> Result<()> {
 	if sprint.status == SprintStatus::Ended {
 		debug!("sprint ended, marking it as summaried");
 		sprint.update_status(app, SprintStatus::Summaried).await?;
@@ -73,3 +78,20 @@ async fn update_status(sprint: &Sprint, app: App) -> Result<()> {
 
 	Ok(())
 }
+
+/// The sprint's summary, followed by totals for its chain if it was the last round.
+async fn summary_text(sprint: &Sprint, app: App) -> Result<String> {
+	let mut summary = sprint.summary_text(app.clone()).await?;
+
+	if let Some(chain_id) = sprint.chain_id {
+		let chain = SprintChain::get(app.clone(), chain_id).await?;
+		if chain.is_last_round(sprint) {
+			if let Some(totals) = chain.summary_text(app).await? {
+				summary.push_str("\n\n");
+				summary.push_str(&totals);
+			}
+		}
+	}
+
+	Ok(summary)
+}
@@ -0,0 +1,68 @@
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use twilight_model::{application::interaction::Interaction, http::attachment::Attachment};
+use uuid::Uuid;
+
+use crate::{
+	bot::{
+		context::{GenericResponse, GenericResponseData},
+		utils::time::ChronoDateTimeExt,
+		App,
+	},
+	db::sprint::Sprint,
+};
+
+use super::{Action, ActionClass, Args};
+
+/// The summary of a sprint, rendered as an image.
+///
+/// Attachment bytes aren't serialised, so the card is rendered when the action is handled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintSummaryCard {
+	pub response: GenericResponse,
+	pub sprint: Uuid,
+}
+
+impl SprintSummaryCard {
+	#[tracing::instrument(name = "SprintSummaryCard", skip(app, interaction))]
+	pub async fn new(app: App, interaction: &Interaction, sprint: Sprint) -> Result<Action> {
+		super::sprint_summary::update_status(&sprint, app).await?;
+
+		Ok(ActionClass::SprintSummaryCard(Box::new(Self {
+			response: GenericResponse::from_interaction(
+				interaction,
+				GenericResponseData {
+					ephemeral: sprint.private,
+					..Default::default()
+				},
+			)
+			.with_age(sprint.created_at.elapsed()?),
+			sprint: sprint.id,
+		}))
+		.into())
+	}
+
+	#[tracing::instrument(name = "SprintSummaryCard::handle", skip(app))]
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		let sprint = Sprint::get(app.clone(), self.sprint).await?;
+		let card = sprint.summary_card(app.clone()).await?;
+		let png = tokio::task::spawn_blocking(move || card.to_png())
+			.await
+			.into_diagnostic()??;
+
+		app.send_response(GenericResponse {
+			data: GenericResponseData {
+				attachments: vec![Attachment::from_bytes(
+					format!("sprint-{}.png", sprint.shortid),
+					png,
+					0,
+				)],
+				ephemeral: self.response.data.ephemeral,
+				..Default::default()
+			},
+			..self.response
+		})
+		.await
+		.map(drop)
+	}
+}
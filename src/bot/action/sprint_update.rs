@@ -1,12 +1,13 @@
-use miette::Result;
-use tracing::warn;
+use miette::{Context, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 use uuid::Uuid;
 
 use crate::db::sprint::Sprint;
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintUpdate {
 	sprint: Uuid,
 }
@@ -19,10 +20,18 @@ impl SprintUpdate {
 
 	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
 		let sprint = Sprint::get_current(app.clone(), self.sprint).await?;
-		let content = sprint.status_text(app, false).await?;
+		let Some(announce) = sprint.announce else {
+			debug!("sprint has no announce yet, nothing to update");
+			return Ok(());
+		};
 
-		warn!(?content, "TODO: update announce");
-		Ok(())
-		// app.send_response(self.0).await.map(drop)
+		let content = sprint.announce_text(app.clone()).await?;
+		app.client
+			.update_message(announce.into(), announce.into())
+			.content(Some(&content))
+			.await
+			.into_diagnostic()
+			.wrap_err("update announce")
+			.map(drop)
 	}
 }
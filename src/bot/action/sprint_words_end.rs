@@ -1,4 +1,5 @@
 use miette::{miette, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{Component, TextInput, TextInputStyle},
@@ -15,7 +16,7 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintWordsEnd {
 	pub id: Id<InteractionMarker>,
 	pub token: String,
@@ -42,6 +43,7 @@ impl SprintWordsEnd {
 		}
 
 		let Sprint { id, shortid, .. } = sprint;
+		let plural = sprint.measure.plural();
 		let participant = sprint.participant(app.clone(), self.member).await?;
 
 		app.interaction_client()
@@ -53,10 +55,10 @@ impl SprintWordsEnd {
 					data: Some(
 						InteractionResponseDataBuilder::new()
 							.custom_id(format!("sprint:set-words:end:{id}"))
-							.title(format!("Ending words for sprint {shortid}"))
+							.title(format!("Ending {plural} for sprint {shortid}"))
 							.components(action_row(vec![Component::TextInput(TextInput {
 								custom_id: "words".into(),
-								label: "How many words did you end up with?".into(),
+								label: format!("How many {plural} did you end up with?"),
 								max_length: Some(20),
 								min_length: Some(1),
 								placeholder: None,
@@ -1,4 +1,5 @@
 use miette::{miette, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{Component, TextInput, TextInputStyle},
@@ -15,7 +16,7 @@ use crate::{
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintWordsStart {
 	pub id: Id<InteractionMarker>,
 	pub token: String,
@@ -42,7 +43,15 @@ impl SprintWordsStart {
 		}
 
 		let Sprint { id, shortid, .. } = sprint;
+		let plural = sprint.measure.plural();
 		let participant = sprint.participant(app.clone(), self.member).await?;
+		let words_start = match participant.words_start {
+			Some(words) => words,
+			None => sprint
+				.carried_words(app.clone(), self.member)
+				.await?
+				.unwrap_or(0),
+		};
 
 		app.interaction_client()
 			.create_response(
@@ -53,16 +62,16 @@ impl SprintWordsStart {
 					data: Some(
 						InteractionResponseDataBuilder::new()
 							.custom_id(format!("sprint:set-words:start:{id}"))
-							.title(format!("Starting words for sprint {shortid}"))
+							.title(format!("Starting {plural} for sprint {shortid}"))
 							.components(action_row(vec![Component::TextInput(TextInput {
 								custom_id: "words".into(),
-								label: "How many words are you starting with?".into(),
+								label: format!("How many {plural} are you starting with?"),
 								max_length: Some(20),
 								min_length: Some(1),
 								placeholder: None,
 								required: Some(true),
 								style: TextInputStyle::Short,
-								value: Some(participant.words_start.unwrap_or(0).to_string()),
+								value: Some(words_start.to_string()),
 							})]))
 							.build(),
 					),
@@ -1,4 +1,5 @@
 use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	application::interaction::Interaction,
 	channel::message::component::{ActionRow, Component, TextInput, TextInputStyle},
@@ -12,7 +13,7 @@ use crate::db::member::Member;
 
 use super::{Action, ActionClass, Args};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimezoneModal {
 	pub id: Id<InteractionMarker>,
 	pub token: String,
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use miette::Result;
+use serde::{Deserialize, Serialize};
+use tokio::spawn;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+	bot::{context::Timer, App},
+	db::{pending_tally::PendingTally, trackbear_login::TrackbearLogin},
+	error_ext::ErrorExt,
+	trackbear::client::{is_timeout, is_transient},
+};
+
+use super::{Action, ActionClass, Args};
+
+/// Wait before the first retry, doubled for each retry after that.
+const RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Give up after this many retries, which is about a day with the doubling delay.
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Try again to save a tally which TrackBear couldn't take when it was recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackbearRetryTally(Uuid);
+
+impl TrackbearRetryTally {
+	#[tracing::instrument(name = "TrackbearRetryTally")]
+	pub fn new(pending: &PendingTally) -> Action {
+		ActionClass::TrackbearRetryTally(Box::new(Self(pending.id))).into()
+	}
+
+	/// The timer key for the next attempt at saving the tally.
+	pub fn timer_key(pending: &PendingTally) -> String {
+		format!("pending-tally:{}", pending.id)
+	}
+
+	/// Set up a timer for the next attempt at saving the tally.
+	pub async fn schedule(app: App, pending: &PendingTally) -> Result<()> {
+		let delay = RETRY_DELAY * 2_u32.pow(pending.attempts.clamp(0, MAX_ATTEMPTS) as u32);
+		debug!(?pending.id, ?delay, "scheduling pending tally retry");
+		app.send_timer(
+			Timer::new_after(delay, Self::new(pending))?.with_key(Self::timer_key(pending)),
+		)
+		.await
+	}
+
+	pub async fn handle(self, Args { app, .. }: Args) -> Result<()> {
+		// with retries and timeouts this can take a while, so don't hold up the other timers
+		spawn(async move {
+			self.retry(app)
+				.await
+				.unwrap_or_else(|err| error!("{err:?}"))
+		});
+		Ok(())
+	}
+
+	async fn retry(self, app: App) -> Result<()> {
+		let Some(mut pending) = PendingTally::get(app.clone(), self.0).await? else {
+			debug!("pending tally is gone, nothing to retry");
+			return Ok(());
+		};
+
+		let client = TrackbearLogin::get_for_member(app.clone(), pending.member)
+			.await?
+			.and_then(|login| login.client_unchecked(app.clone()));
+		let Some(client) = client else {
+			debug!(?pending.member, "member logged out of TrackBear, dropping pending tally");
+			return pending.delete(app).await;
+		};
+
+		let request = &pending.request;
+		let what = format!(
+			"{:+} {} for {}",
+			request.count,
+			request.measure.plural(),
+			request.date
+		);
+
+		match client.create_tally(request.clone()).await {
+			Ok(tally) => {
+				info!(?pending.id, ?tally.id, "saved pending tally");
+				let member = pending.member;
+				pending.delete(app.clone()).await?;
+				app.send_dm(
+					member,
+					&format!("✅ TrackBear is back, and I've saved your {what}!"),
+				)
+				.await
+				.log()
+				.ok();
+			}
+			Err(err) if is_transient(&err) && pending.attempts + 1 < MAX_ATTEMPTS => {
+				debug!(?pending.id, ?err, "TrackBear still unavailable, retrying later");
+				pending.failed(app.clone(), &err.to_string()).await?;
+				Self::schedule(app, &pending).await?;
+			}
+			Err(err) => {
+				warn!(?pending.id, ?err, "giving up on pending tally");
+				let member = pending.member;
+				pending.delete(app.clone()).await?;
+				// the last try may have gone through, so adding it again could count it twice
+				let content = if is_timeout(&err) {
+					format!(
+						"❓ I couldn't tell whether TrackBear saved your {what}. Check `/words tallies` before adding it yourself.\n> {err}"
+					)
+				} else {
+					format!(
+						"❌ I couldn't save your {what} on TrackBear, you'll need to add it yourself.\n> {err}"
+					)
+				};
+				app.send_dm(member, &content).await.log().ok();
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -16,6 +16,7 @@ use crate::{
 		utils::command::{get_integer, get_string},
 		App,
 	},
+	db::guild_settings::GuildSettings,
 	error_ext::ErrorExt,
 };
 
@@ -54,6 +55,14 @@ pub async fn on_command(
 	let items_str =
 		get_string(&command_data.options, "items").ok_or(miette!("need at least one item"))?;
 	debug!(items=?items_str, ?count, "choose arguments");
+	let weighting = match interaction.guild_id {
+		Some(guild_id) => {
+			GuildSettings::get(app.clone(), guild_id.get() as _)
+				.await?
+				.choose_weighting
+		}
+		None => GuildSettings::default().choose_weighting,
+	};
 	app.do_action(CommandAck::new(interaction)).await.log().ok();
 
 	let or = Regex::new(r"(?i)\s+or\s+").unwrap();
@@ -66,12 +75,14 @@ pub async fn on_command(
 
 	let single = items.len() == 1;
 
-	if let Some(write) = items.iter().find(|i| i.eq_ignore_ascii_case("write")) {
-		items.push(write);
-	}
+	if weighting {
+		if let Some(write) = items.iter().find(|i| i.eq_ignore_ascii_case("write")) {
+			items.push(write);
+		}
 
-	if let Some(sprint) = items.iter().find(|i| i.eq_ignore_ascii_case("sprint")) {
-		items.push(sprint);
+		if let Some(sprint) = items.iter().find(|i| i.eq_ignore_ascii_case("sprint")) {
+			items.push(sprint);
+		}
 	}
 
 	if single {
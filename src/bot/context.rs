@@ -2,13 +2,15 @@ use std::{
 	future::IntoFuture,
 	ops::Deref,
 	sync::Arc,
-	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+	time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use miette::{miette, Context, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
 use tokio::{
 	sync::mpsc::Sender,
-	time::{sleep, sleep_until, timeout, Instant as TokioInstant, Sleep},
+	time::{sleep, timeout},
 };
 use tokio_postgres::Client as PgClient;
 use tracing::{debug, error};
@@ -34,7 +36,13 @@ use twilight_model::{
 use twilight_util::builder::InteractionResponseDataBuilder;
 
 use super::action::Action;
-use crate::{config::Config, db::sprint::Sprint, error_ext::ErrorExt, nominare::Nominare};
+use crate::{
+	config::Config,
+	db::{member::Member, scheduled_action::ScheduledAction, sprint::Sprint},
+	error_ext::ErrorExt,
+	nominare::Nominare,
+	trackbear::Trackbear,
+};
 
 #[derive(Clone, Debug)]
 #[repr(transparent)]
@@ -45,20 +53,22 @@ pub struct AppContext {
 	pub config: Config,
 	pub db: PgClient,
 	pub client: Client,
-	pub timer: Sender<Timer>,
+	pub ticker: Sender<()>,
 	pub nominare: Option<Nominare>,
+	pub trackbear: Trackbear,
 }
 
 impl App {
-	pub fn new(mut config: Config, db: PgClient, timer: Sender<Timer>) -> Self {
+	pub fn new(mut config: Config, db: PgClient, ticker: Sender<()>) -> Result<Self> {
 		let client = Client::new(config.discord.token.clone());
-		Self(Arc::new(AppContext {
+		Ok(Self(Arc::new(AppContext {
 			nominare: config.nominare_url.take().map(|url| Nominare::new(&url)),
+			trackbear: Trackbear::new(&config.trackbear)?,
 			config,
 			db,
 			client,
-			timer,
-		}))
+			ticker,
+		})))
 	}
 
 	pub fn interaction_client(&self) -> InteractionClient<'_> {
@@ -73,7 +83,49 @@ impl App {
 	}
 
 	pub async fn send_timer(&self, timing: Timer) -> Result<()> {
-		self.timer.send(timing).await.into_diagnostic()
+		ScheduledAction::schedule(self.clone(), timing).await?;
+		self.wake_ticker();
+		Ok(())
+	}
+
+	/// Cancel the pending timer with this key, if any.
+	pub async fn cancel_timer(&self, key: &str) -> Result<()> {
+		ScheduledAction::cancel(self.clone(), key).await
+	}
+
+	/// Cancel all pending timers with keys starting with this prefix.
+	pub async fn cancel_timers(&self, prefix: &str) -> Result<()> {
+		ScheduledAction::cancel_prefix(self.clone(), prefix).await
+	}
+
+	async fn dm_channel(&self, member: Member) -> Result<Id<ChannelMarker>> {
+		self.client
+			.create_private_channel(member.into())
+			.await
+			.into_diagnostic()?
+			.model()
+			.await
+			.into_diagnostic()
+			.map(|channel| channel.id)
+	}
+
+	/// Send a direct message to the member.
+	pub async fn send_dm(&self, member: Member, content: &str) -> Result<Message> {
+		self.client
+			.create_message(self.dm_channel(member).await?)
+			.content(content)
+			.await
+			.into_diagnostic()
+			.wrap_err("dm exec")?
+			.model()
+			.await
+			.into_diagnostic()
+			.wrap_err("dm response")
+	}
+
+	fn wake_ticker(&self) {
+		// if the buffer is full the ticker is already going to wake up
+		self.ticker.try_send(()).ok();
 	}
 
 	#[tracing::instrument]
@@ -123,6 +175,20 @@ impl App {
 				.await
 				.into_diagnostic()
 				.wrap_err("message response")
+		} else if let Some(member) = response.dm {
+			debug!("posting to DM");
+			let data = GenericResponseData {
+				ephemeral: false,
+				..response.data
+			};
+			data.incept_message(self.client.create_message(self.dm_channel(member).await?))?
+				.await
+				.into_diagnostic()
+				.wrap_err("dm exec")?
+				.model()
+				.await
+				.into_diagnostic()
+				.wrap_err("dm response")
 		} else {
 			error!("no channel to post to");
 			Err(miette!("cannot post response, possibly a bug?"))
@@ -220,13 +286,16 @@ impl Deref for App {
 	}
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GenericResponse {
 	pub channel: Option<Id<ChannelMarker>>,
 	pub interaction: Option<Id<InteractionMarker>>,
 	pub token: Option<String>,
 	pub message: Option<MessageForm>,
 	pub data: GenericResponseData,
+	/// Who to DM if the interaction is too old, when there's no channel to post to.
+	#[serde(default)]
+	pub dm: Option<Member>,
 }
 
 impl GenericResponse {
@@ -237,16 +306,36 @@ impl GenericResponse {
 			token: Some(interaction.token.clone()),
 			message: None,
 			data,
+			dm: None,
 		}
 	}
 
 	pub fn from_sprint(sprint: &Sprint, data: GenericResponseData) -> Self {
+		if sprint.private {
+			// only ever to the host: ephemerally while the interaction lasts, then by DM
+			return Self {
+				channel: None,
+				interaction: None,
+				token: sprint.interaction_token.clone(),
+				message: None,
+				data: GenericResponseData {
+					ephemeral: true,
+					..data
+				},
+				dm: sprint.host,
+			};
+		}
+
 		Self {
-			channel: sprint.announce.map(|msg| msg.into()),
+			channel: sprint
+				.announce
+				.map(|msg| msg.into())
+				.or_else(|| sprint.channel.map(|chan| chan.into())),
 			interaction: None,
-			token: Some(sprint.interaction_token.clone()),
+			token: sprint.interaction_token.clone(),
 			message: sprint.announce.map(MessageForm::Db),
 			data,
+			dm: None,
 		}
 	}
 
@@ -261,13 +350,13 @@ impl GenericResponse {
 	}
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MessageForm {
 	Discord(Message),
 	Db(crate::db::message::Message),
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GenericResponseData {
 	pub ephemeral: bool,
 	pub content: Option<String>,
@@ -341,26 +430,31 @@ impl GenericResponseData {
 
 #[derive(Clone, Debug)]
 pub struct Timer {
-	pub until: TokioInstant,
+	pub key: Option<String>,
+	pub until: DateTime<Utc>,
 	pub payload: Action,
 }
 
 impl Timer {
-	pub fn new_at(time: Instant, payload: Action) -> Self {
+	pub fn new_at(time: DateTime<Utc>, payload: Action) -> Self {
 		Self {
-			until: time.into(),
+			key: None,
+			until: time,
 			payload,
 		}
 	}
 
 	pub fn new_after(duration: Duration, payload: Action) -> Result<Self> {
-		Instant::now()
-			.checked_add(duration)
+		ChronoDuration::from_std(duration)
+			.ok()
+			.and_then(|duration| Utc::now().checked_add_signed(duration))
 			.ok_or_else(|| miette!("cannot schedule that far into the future"))
 			.map(|time| Self::new_at(time, payload))
 	}
 
-	pub fn to_sleep(&self) -> Sleep {
-		sleep_until(self.until)
+	/// Set a key for the timer, which replaces any pending timer with the same key.
+	pub fn with_key(mut self, key: impl Into<String>) -> Self {
+		self.key = Some(key.into());
+		self
 	}
 }
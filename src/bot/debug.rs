@@ -21,8 +21,13 @@ use twilight_util::builder::{
 use uuid::Uuid;
 
 use crate::{
-	bot::{action::ComponentAck, App},
-	db::error::Error,
+	bot::{
+		action::{CommandAck, ComponentAck},
+		context::{GenericResponse, GenericResponseData},
+		utils::time::ChronoDateTimeExt,
+		App,
+	},
+	db::{error::Error, scheduled_action::ScheduledAction},
 	error_ext::ErrorExt,
 };
 
@@ -34,6 +39,10 @@ pub fn command() -> Result<Command> {
 		CommandType::ChatInput,
 	)
 	.option(SubCommandBuilder::new("error", "Throw an error"))
+	.option(SubCommandBuilder::new(
+		"timers",
+		"List pending timers (maintainer only)",
+	))
 	.validate()
 	.into_diagnostic()
 	.map(|cmd| cmd.build())
@@ -56,6 +65,9 @@ pub async fn on_command(
 		Some(("error", opts)) => throw_error(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: error")?,
+		Some(("timers", _)) => list_timers(app.clone(), interaction)
+			.await
+			.wrap_err("command: timers")?,
 		Some((other, _)) => warn!("unhandled debug subcommand: {other}"),
 		_ => error!("unreachable bare debug command"),
 	}
@@ -89,6 +101,49 @@ async fn throw_error(
 	Err(miette!("test error"))
 }
 
+async fn list_timers(app: App, interaction: &Interaction) -> Result<()> {
+	let maintainer = app.config.discord.maintainer_id.map(Id::new);
+	if maintainer.is_none() || interaction.author_id() != maintainer {
+		return Err(miette!("only the bot maintainer can list timers"));
+	}
+
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let timers = ScheduledAction::get_all(app.clone()).await?;
+	let content = if timers.is_empty() {
+		"No pending timers.".to_string()
+	} else {
+		let mut content = format!("{} pending timers:", timers.len());
+		for timer in timers {
+			let line = format!(
+				"\n{due} `{action}` {key}",
+				due = timer.due_at.discord_format('R'),
+				action = timer.action.name(),
+				key = timer.key.as_deref().unwrap_or("(no key)"),
+			);
+			if content.len() + line.len() > 2000 {
+				break;
+			}
+			content.push_str(&line);
+		}
+		content
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
 async fn ping_maintainer(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
 	app.do_action(ComponentAck::ephemeral(interaction))
 		.await
@@ -0,0 +1,194 @@
+use std::time::Duration as StdDuration;
+
+use chrono::Utc;
+use miette::{Context, IntoDiagnostic, Result};
+use tokio::task::spawn;
+use tracing::{debug, error, info};
+use twilight_gateway::{EventTypeFlags, Shard, ShardId, StreamExt};
+use twilight_http::request::channel::reaction::RequestReactionType;
+use twilight_model::{channel::message::Message, gateway::event::Event};
+
+use crate::{
+	bot::{action::SprintSummary, context::Timer, App},
+	db::{channel::Channel, guild_settings::GuildSettings, member::Member, sprint::Sprint},
+};
+
+#[tracing::instrument(skip_all)]
+pub async fn listen(app: App) -> Result<()> {
+	let mut shard = Shard::new(
+		ShardId::ONE,
+		app.config.discord.token.clone(),
+		app.config.discord.intents.to_intent(),
+	);
+
+	info!("created shard");
+
+	loop {
+		let event = match shard.next_event(EventTypeFlags::all()).await {
+			Some(Ok(event)) => event,
+			Some(Err(err)) => {
+				error!(?err, "error receiving event");
+				continue;
+			}
+			None => {
+				info!("shard stream ended");
+				break;
+			}
+		};
+
+		debug!(?event, "spawning off to handle event");
+
+		let app = app.clone();
+		spawn(async move {
+			match event {
+				Event::InteractionCreate(ic) => super::handle_interaction(app.clone(), &ic.0)
+					.await
+					.wrap_err("event: interaction-create"),
+				Event::MessageCreate(msg) => on_message(app.clone(), &msg.0)
+					.await
+					.wrap_err("event: message-create"),
+				_ => Ok(()),
+			}
+			.unwrap_or_else(|err| error!("{err:?}"))
+		});
+	}
+
+	info!("listener exiting");
+	Ok(())
+}
+
+/// A word count typed in chat by a sprint participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatWords {
+	/// A bare number: starting words before the sprint starts, ending words after.
+	Count(i32),
+	Start(i32),
+	End(i32),
+	/// Words written during the sprint, on top of the starting words.
+	Written(i32),
+}
+
+impl ChatWords {
+	/// Parse `1234`, `start 1234`, `end 1234`, or `+350`; anything else is just chatter.
+	fn parse(content: &str) -> Option<Self> {
+		let content = content.trim();
+		let (words, number): (fn(i32) -> Self, &str) =
+			if let Some(number) = content.strip_prefix('+') {
+				(Self::Written, number)
+			} else if let Some((keyword, number)) = content.split_once(char::is_whitespace) {
+				match keyword.to_lowercase().as_str() {
+					"start" => (Self::Start, number),
+					"end" => (Self::End, number),
+					_ => return None,
+				}
+			} else {
+				(Self::Count, content)
+			};
+
+		// allow thousands separators, like 1,234
+		let number = number.trim().replace(',', "");
+		if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+
+		number.parse().ok().map(words)
+	}
+}
+
+/// Set sprint words from a message, if the guild allows it and the author is sprinting here.
+#[tracing::instrument(skip_all, fields(message = %message.id))]
+async fn on_message(app: App, message: &Message) -> Result<()> {
+	if message.author.bot {
+		return Ok(());
+	}
+
+	let Some(words) = ChatWords::parse(&message.content) else {
+		return Ok(());
+	};
+
+	// not from a guild, so not in a sprint channel
+	let Ok(member) = Member::try_from(message) else {
+		return Ok(());
+	};
+
+	if !GuildSettings::get(app.clone(), member.guild_id)
+		.await?
+		.chat_words
+	{
+		return Ok(());
+	}
+
+	let channel = Channel::try_from(message)?;
+	let Some(sprint) = Sprint::get_latest_joined_in_channel(app.clone(), channel, member).await?
+	else {
+		debug!(?member, "member isn't in a sprint in this channel");
+		return Ok(());
+	};
+
+	let participant = sprint.participant(app.clone(), member).await?;
+	let (column, words) = match words {
+		ChatWords::Start(n) => ("words_start", n),
+		ChatWords::End(n) => ("words_end", n),
+		ChatWords::Written(n) => {
+			let Some(total) = participant.words_start.unwrap_or(0).checked_add(n) else {
+				debug!(?member, %n, "written words don't fit, ignoring");
+				return Ok(());
+			};
+			("words_end", total)
+		}
+		ChatWords::Count(n) if sprint.starting_at > Utc::now() => ("words_start", n),
+		ChatWords::Count(n) => ("words_end", n),
+	};
+
+	debug!(?sprint.id, %column, %words, "setting sprint words from chat");
+	sprint.set_words(app.clone(), member, words, column).await?;
+
+	app.client
+		.create_reaction(
+			message.channel_id,
+			message.id,
+			&RequestReactionType::Unicode { name: "✅" },
+		)
+		.await
+		.into_diagnostic()
+		.wrap_err("acknowledge words with a reaction")?;
+
+	// the TrackBear prompt is ephemeral, so it's only offered when words are set via the modal
+	if column == "words_end"
+		&& sprint.ending_at() <= Utc::now()
+		&& sprint
+			.all_participants_have_ending_words(app.clone())
+			.await?
+	{
+		let key = sprint.timer_key("summary");
+		app.send_timer(
+			Timer::new_after(
+				StdDuration::from_secs(1),
+				SprintSummary::new_from_db(app.clone(), sprint).await?,
+			)?
+			.with_key(key),
+		)
+		.await?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn parse_chat_words() {
+		assert_eq!(ChatWords::parse("1234"), Some(ChatWords::Count(1234)));
+		assert_eq!(ChatWords::parse(" 1,234 "), Some(ChatWords::Count(1234)));
+		assert_eq!(ChatWords::parse("+350"), Some(ChatWords::Written(350)));
+		assert_eq!(ChatWords::parse("End 1234"), Some(ChatWords::End(1234)));
+		assert_eq!(ChatWords::parse("start 0"), Some(ChatWords::Start(0)));
+		assert_eq!(ChatWords::parse("+-5"), None);
+		assert_eq!(ChatWords::parse("-5"), None);
+		assert_eq!(ChatWords::parse("I wrote 500 words"), None);
+		assert_eq!(ChatWords::parse("end"), None);
+		assert_eq!(ChatWords::parse(""), None);
+	}
+}
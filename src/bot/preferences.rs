@@ -0,0 +1,208 @@
+use miette::{miette, Context, IntoDiagnostic, Result};
+use tracing::{error, warn};
+use twilight_model::application::{
+	command::{Command, CommandType},
+	interaction::{
+		application_command::{CommandData, CommandDataOption, CommandOptionValue},
+		Interaction,
+	},
+};
+use twilight_util::builder::command::{
+	BooleanBuilder, CommandBuilder, IntegerBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	bot::{
+		action::CommandAck,
+		context::{GenericResponse, GenericResponseData},
+		utils::command::{get_boolean, get_integer},
+		App,
+	},
+	db::{member::Member, user_preference::UserPreference},
+	error_ext::ErrorExt,
+};
+
+#[tracing::instrument]
+pub fn command() -> Result<Command> {
+	CommandBuilder::new(
+		"preferences",
+		"View and change your preferences in this server",
+		CommandType::ChatInput,
+	)
+	.option(
+		SubCommandBuilder::new(
+			"leaderboard",
+			"Choose whether you appear on sprint leaderboards",
+		)
+		.option(
+			BooleanBuilder::new("show", "Whether to show you on sprint leaderboards")
+				.required(true),
+		),
+	)
+	.option(
+		SubCommandBuilder::new(
+			"carry-words",
+			"Choose whether your last total becomes your starting words when you join a sprint",
+		)
+		.option(
+			BooleanBuilder::new(
+				"enabled",
+				"Whether to carry your words into your next sprints",
+			)
+			.required(true),
+		),
+	)
+	.option(
+		SubCommandBuilder::new(
+			"reminders",
+			"Choose whether to get DMs before sprints you joined start and after they end",
+		)
+		.option(BooleanBuilder::new("enabled", "Whether to get sprint reminders").required(true))
+		.option(
+			IntegerBuilder::new(
+				"minutes",
+				"How many minutes before a sprint starts to remind you (defaults to 10)",
+			)
+			.min_value(1)
+			.max_value(120),
+		),
+	)
+	.validate()
+	.into_diagnostic()
+	.map(|cmd| cmd.build())
+}
+
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	command_data: &CommandData,
+) -> Result<()> {
+	let subcmd = command_data.options.iter().find_map(|opt| {
+		if let CommandOptionValue::SubCommand(ref sub) = opt.value {
+			Some((opt.name.as_str(), sub.as_slice()))
+		} else {
+			None
+		}
+	});
+
+	match subcmd {
+		Some(("leaderboard", opts)) => set_leaderboard(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: leaderboard")?,
+		Some(("carry-words", opts)) => set_carry_words(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: carry-words")?,
+		Some(("reminders", opts)) => set_reminders(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: reminders")?,
+		Some((other, _)) => warn!("unhandled preferences subcommand: {other}"),
+		_ => error!("unreachable bare preferences command"),
+	}
+
+	Ok(())
+}
+
+async fn set_leaderboard(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let show = get_boolean(options, "show").ok_or_else(|| miette!("show is a required field"))?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	UserPreference::get_or_create(app.clone(), member)
+		.await?
+		.set_leaderboard_opt_out(app.clone(), !show)
+		.await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(if show {
+				"🏆 You'll appear on sprint leaderboards in this server.".to_string()
+			} else {
+				"🙈 You won't appear on sprint leaderboards in this server anymore.".to_string()
+			}),
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+async fn set_carry_words(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let enabled =
+		get_boolean(options, "enabled").ok_or_else(|| miette!("enabled is a required field"))?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	UserPreference::get_or_create(app.clone(), member)
+		.await?
+		.set_carry_words(app.clone(), enabled)
+		.await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(if enabled {
+				"📎 When you join a sprint, your starting words will be your last ending words (or your TrackBear project's total)."
+					.to_string()
+			} else {
+				"✂️ You'll give your starting words yourself when you join a sprint.".to_string()
+			}),
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+async fn set_reminders(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let enabled =
+		get_boolean(options, "enabled").ok_or_else(|| miette!("enabled is a required field"))?;
+	let minutes = get_integer(options, "minutes");
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let prefs = UserPreference::get_or_create(app.clone(), member).await?;
+	let minutes = match minutes {
+		Some(minutes) => minutes.try_into().into_diagnostic()?,
+		None => prefs.reminder_minutes,
+	};
+	prefs.set_reminders(app.clone(), enabled, minutes).await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(if enabled {
+				format!("⏰ You'll get a DM {minutes} minutes before sprints you joined start, and after they end if you haven't given your words.")
+			} else {
+				"💤 You won't get sprint reminders anymore.".to_string()
+			}),
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
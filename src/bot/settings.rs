@@ -0,0 +1,336 @@
+use chrono::NaiveTime;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use tracing::{error, warn};
+use twilight_model::{
+	application::{
+		command::{Command, CommandType},
+		interaction::{
+			application_command::{CommandData, CommandDataOption, CommandOptionValue},
+			Interaction,
+		},
+	},
+	guild::Permissions,
+};
+use twilight_util::builder::command::{
+	BooleanBuilder, CommandBuilder, IntegerBuilder, RoleBuilder, StringBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	bot::{
+		action::CommandAck,
+		sprint::export,
+		utils::{
+			command::{get_boolean, get_integer, get_role, get_string},
+			permissions::require_manage_guild,
+			respond_ephemeral,
+			time::parse_when_relative_to,
+		},
+		App,
+	},
+	db::{guild_settings::GuildSettings, member::Member},
+	error_ext::ErrorExt,
+};
+
+#[tracing::instrument]
+pub fn command() -> Result<Command> {
+	CommandBuilder::new(
+		"settings",
+		"View and change settings for this server (admins only)",
+		CommandType::ChatInput,
+	)
+	.default_member_permissions(Permissions::MANAGE_GUILD)
+	.option(SubCommandBuilder::new(
+		"show",
+		"Show the settings for this server",
+	))
+	.option(
+		SubCommandBuilder::new(
+			"chat-words",
+			"Choose whether sprinters can give their word counts by typing them in chat",
+		)
+		.option(
+			BooleanBuilder::new(
+				"enabled",
+				"Whether to read word counts like 1234, +350, or end 1234 from chat",
+			)
+			.required(true),
+		),
+	)
+	.option(
+		SubCommandBuilder::new("sprint", "Change the defaults for sprints")
+			.option(
+				IntegerBuilder::new("duration", "Default duration of sprints in minutes")
+					.min_value(1)
+					.max_value(1440),
+			)
+			.option(StringBuilder::new(
+				"when",
+				"Default start of sprints, either in clock time (08:30), or in relative time (15m)",
+			))
+			.option(
+				IntegerBuilder::new(
+					"warning",
+					"Seconds before the start and end of sprints to warn (0 for no warnings)",
+				)
+				.min_value(0)
+				.max_value(600),
+			)
+			.option(
+				IntegerBuilder::new(
+					"grace",
+					"Minutes after sprints end to wait for everyone's ending words before summarizing anyway",
+				)
+				.min_value(1)
+				.max_value(1440),
+			),
+	)
+	.option(
+		SubCommandBuilder::new(
+			"sprinters",
+			"Choose a role to mention when sprints are announced and about to start",
+		)
+		.option(RoleBuilder::new(
+			"role",
+			"Role to mention (leave out to stop mentioning a role)",
+		)),
+	)
+	.option(export::with_options(SubCommandBuilder::new(
+		"export",
+		"Download everyone's sprints in this server as a file",
+	)))
+	.option(
+		SubCommandBuilder::new("choose", "Change how /choose picks").option(
+			BooleanBuilder::new(
+				"weighting",
+				"Whether \"write\" and \"sprint\" are more likely to be chosen",
+			)
+			.required(true),
+		),
+	)
+	.validate()
+	.into_diagnostic()
+	.map(|cmd| cmd.build())
+}
+
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	command_data: &CommandData,
+) -> Result<()> {
+	let member = Member::try_from(interaction)?;
+	require_manage_guild(app.clone(), member).await?;
+
+	let subcmd = command_data.options.iter().find_map(|opt| {
+		if let CommandOptionValue::SubCommand(ref sub) = opt.value {
+			Some((opt.name.as_str(), sub.as_slice()))
+		} else {
+			None
+		}
+	});
+
+	match subcmd {
+		Some(("show", _)) => show(app.clone(), interaction, member)
+			.await
+			.wrap_err("command: show")?,
+		Some(("chat-words", opts)) => set_chat_words(app.clone(), interaction, member, opts)
+			.await
+			.wrap_err("command: chat-words")?,
+		Some(("sprint", opts)) => set_sprint(app.clone(), interaction, member, opts)
+			.await
+			.wrap_err("command: sprint")?,
+		Some(("sprinters", opts)) => set_sprinters(app.clone(), interaction, member, opts)
+			.await
+			.wrap_err("command: sprinters")?,
+		Some(("choose", opts)) => set_choose(app.clone(), interaction, member, opts)
+			.await
+			.wrap_err("command: choose")?,
+		Some(("export", opts)) => export::on_guild_command(app.clone(), interaction, member, opts)
+			.await
+			.wrap_err("command: export")?,
+		Some((other, _)) => warn!("unhandled settings subcommand: {other}"),
+		_ => error!("unreachable bare settings command"),
+	}
+
+	Ok(())
+}
+
+async fn show(app: App, interaction: &Interaction, member: Member) -> Result<()> {
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("⚙️ {}", describe(&settings)),
+		Vec::new(),
+	)
+	.await
+}
+
+async fn set_chat_words(
+	app: App,
+	interaction: &Interaction,
+	member: Member,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let enabled =
+		get_boolean(options, "enabled").ok_or_else(|| miette!("enabled is a required field"))?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let mut settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+	settings.chat_words = enabled;
+	settings.save(app.clone()).await?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		if enabled {
+			"⌨️ Sprinters can now type their word counts in the sprint's channel: `1234` (starting words before the sprint, ending words after), `start 1234`, `end 1234`, or `+350`."
+				.to_string()
+		} else {
+			"🔇 Word counts typed in chat won't be read anymore.".to_string()
+		},
+		Vec::new(),
+	)
+	.await
+}
+
+async fn set_sprint(
+	app: App,
+	interaction: &Interaction,
+	member: Member,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let duration = get_integer(options, "duration");
+	let when = get_string(options, "when").map(str::trim);
+	let warning = get_integer(options, "warning");
+	let grace = get_integer(options, "grace");
+	if duration.is_none() && when.is_none() && warning.is_none() && grace.is_none() {
+		return Err(miette!(
+			"give at least one of duration, when, warning, or grace"
+		));
+	}
+
+	if let Some(when) = when {
+		// the time doesn't matter, only that it parses
+		parse_when_relative_to(NaiveTime::MIN, when)?;
+	}
+
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let mut settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+	if let Some(duration) = duration {
+		settings.sprint_duration = duration.try_into().into_diagnostic()?;
+	}
+	if let Some(when) = when {
+		settings.sprint_when = when.to_string();
+	}
+	if let Some(warning) = warning {
+		settings.sprint_warning = warning.try_into().into_diagnostic()?;
+	}
+	if let Some(grace) = grace {
+		settings.summary_grace = grace.try_into().into_diagnostic()?;
+	}
+	settings.save(app.clone()).await?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("✅ Settings updated. {}", describe(&settings)),
+		Vec::new(),
+	)
+	.await
+}
+
+async fn set_choose(
+	app: App,
+	interaction: &Interaction,
+	member: Member,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let weighting = get_boolean(options, "weighting")
+		.ok_or_else(|| miette!("weighting is a required field"))?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let mut settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+	settings.choose_weighting = weighting;
+	settings.save(app.clone()).await?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("✅ Settings updated. {}", describe(&settings)),
+		Vec::new(),
+	)
+	.await
+}
+
+async fn set_sprinters(
+	app: App,
+	interaction: &Interaction,
+	member: Member,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let role = get_role(options, "role");
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let mut settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+	settings.sprinters_role = role.map(|id| id.get() as _);
+	settings.save(app.clone()).await?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("✅ Settings updated. {}", describe(&settings)),
+		Vec::new(),
+	)
+	.await
+}
+
+fn describe(settings: &GuildSettings) -> String {
+	format!(
+		"Settings for this server:\n\
+		- sprints start in **{when}** and last **{duration}** minutes by default\n\
+		- {warning}\n\
+		- sprints are summarized at most **{grace}** minutes after they end, even if some ending words are missing\n\
+		- word counts typed in chat are **{chat_words}**\n\
+		- /choose **{weighting}** \"write\" and \"sprint\"\n\
+		- {sprinters}",
+		when = settings.sprint_when,
+		duration = settings.sprint_duration,
+		grace = settings.summary_grace,
+		warning = match settings.sprint_warning {
+			0 => "there are **no warnings** before sprints start and end".to_string(),
+			secs => format!("sprinters are warned **{secs}** seconds before sprints start and end"),
+		},
+		chat_words = if settings.chat_words {
+			"read"
+		} else {
+			"ignored"
+		},
+		sprinters = match settings.sprinters_mention() {
+			Some(role) => format!("{role} is mentioned when sprints are announced and start"),
+			None => "no role is mentioned for sprints".to_string(),
+		},
+		weighting = if settings.choose_weighting {
+			"favours"
+		} else {
+			"doesn't favour"
+		},
+	)
+}
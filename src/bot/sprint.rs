@@ -2,10 +2,13 @@
 
 use std::{str::FromStr, time::Duration as StdDuration};
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures_util::future::try_join_all;
+use humantime::format_duration;
+use itertools::Itertools;
 use miette::{miette, Context, IntoDiagnostic, Result};
 use tracing::{debug, error, info, warn};
+use twilight_mention::Mention;
 use twilight_model::application::{
 	command::{Command, CommandType},
 	interaction::{
@@ -16,36 +19,64 @@ use twilight_model::application::{
 	},
 };
 use twilight_util::builder::command::{
-	CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+	BooleanBuilder, CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
 };
 use uuid::Uuid;
 
 use crate::{
 	bot::{
 		action::{
-			CommandAck, ComponentAck, SprintAnnounce, SprintCancelled, SprintEnd, SprintEndWarning,
-			SprintJoined, SprintLeft, SprintSaveWords, SprintStart, SprintStartWarning,
-			SprintSummary, SprintUpdate, SprintWordsEnd, SprintWordsStart,
+			sprint_reminder::ReminderKind, CommandAck, ComponentAck, SprintAnnounce,
+			SprintCancelled, SprintCloseOut, SprintEditModal, SprintEnd, SprintEndWarning,
+			SprintJoinModal, SprintJoined, SprintLeft, SprintReminder, SprintSaveWords,
+			SprintStart, SprintStartWarning, SprintSummary, SprintSummaryCard, SprintUpdate,
+			SprintWordsEnd, SprintWordsStart,
 		},
 		context::{GenericResponse, GenericResponseData, Timer},
 		utils::{
-			command::{get_integer, get_string},
-			time::parse_when_relative_to,
+			command::{get_boolean, get_integer, get_string},
+			time::{parse_when_relative_to, ChronoDateTimeExt, ChronoDurationExt},
 		},
 		words::{save_words as save_words_action, SaveWords},
 		App,
 	},
 	db::{
 		channel::Channel,
+		guild_settings::GuildSettings,
 		member::Member,
 		project::Project,
-		sprint::{Sprint, SprintStatus},
+		sprint::{Sprint, SprintMeasure, SprintStatus},
+		sprint_chain::SprintChain,
+		sprint_stats::{SprintHistoryEntry, SprintStats},
 		trackbear_login::TrackbearLogin,
 		user_preference::UserPreference,
 	},
 	error_ext::ErrorExt,
+	trackbear::project::TallyDetails,
 };
 
+pub mod export;
+pub mod host;
+pub mod leaderboard;
+pub mod notify;
+pub mod schedule;
+
+use host::require_host;
+
+/// Longest a sprint can go for, the same as the longest default duration in the settings.
+const MAX_SPRINT_MINUTES: i64 = 1440;
+
+/// Check a sprint duration given in minutes.
+fn sprint_duration(minutes: i64) -> Result<Duration> {
+	if (1..=MAX_SPRINT_MINUTES).contains(&minutes) {
+		Ok(Duration::minutes(minutes))
+	} else {
+		Err(miette!(
+			"duration must be between 1 and {MAX_SPRINT_MINUTES} minutes"
+		))
+	}
+}
+
 #[tracing::instrument]
 pub fn command() -> Result<Command> {
 	CommandBuilder::new(
@@ -58,18 +89,88 @@ pub fn command() -> Result<Command> {
 			.option(
 				StringBuilder::new(
 					"when",
-					"When to start the sprint, either in clock time (08:30), or in relative time (15m)",
+					"When to start the sprint, either in clock time (08:30), or in relative time (defaults to 15m)",
 				)
-				.required(true)
 			)
 			.option(
 				IntegerBuilder::new(
 					"duration",
 					"Duration of the sprint in minutes (defaults to 20)",
 				)
+				.min_value(1)
+				.max_value(MAX_SPRINT_MINUTES)
+			)
+			.option(
+				IntegerBuilder::new(
+					"rounds",
+					"Run this many sprints back to back, with a break in between (defaults to 1)",
+				)
+				.min_value(1)
+				.max_value(12)
+			)
+			.option(
+				IntegerBuilder::new(
+					"break",
+					"Break between rounds in minutes (defaults to 5)",
+				)
+				.min_value(1)
+				.max_value(60)
+			)
+			.option(
+				StringBuilder::new(
+					"measure",
+					"What to count during the sprint (defaults to words)",
+				)
+				.choices(vec![
+					("Words", "word"),
+					("Pages", "page"),
+					("Chapters", "chapter"),
+					("Scenes", "scene"),
+					("Lines", "line"),
+				])
+			)
+			.option(
+				StringBuilder::new(
+					"teams",
+					"Make it a word war between teams, with names like: Red, Blue",
+				)
+			)
+			.option(BooleanBuilder::new(
+				"private",
+				"Sprint on your own, with messages only you can see",
+			))
+	)
+	.option(
+		SubCommandBuilder::new("edit", "Change when a sprint starts or how long it lasts")
+			.option(
+				IntegerBuilder::new(
+					"sprint",
+					"Short sprint ID, like 3349",
+				)
+				.required(true)
+			)
+			.option(
+				StringBuilder::new(
+					"when",
+					"New start time, either in clock time (08:30), or in relative time (15m)",
+				)
+			)
+			.option(
+				IntegerBuilder::new(
+					"duration",
+					"New duration of the sprint in minutes",
+				)
+				.min_value(1)
+				.max_value(MAX_SPRINT_MINUTES)
 			)
 	)
-	.option(SubCommandBuilder::new("list", "List all current sprints"))
+	.option(
+		SubCommandBuilder::new("list", "List all current sprints in this server")
+			.option(BooleanBuilder::new(
+				"here",
+				"Only list sprints in this channel",
+			))
+	)
 	.option(
 		SubCommandBuilder::new("summary", "Show the summary of a sprint")
 			.option(
@@ -79,7 +180,27 @@ pub fn command() -> Result<Command> {
 				)
 				.required(true)
 			)
+			.option(BooleanBuilder::new(
+				"image",
+				"Render the summary as an image card",
+			))
 	)
+	.option(
+		SubCommandBuilder::new("stats", "Show your sprint statistics and history")
+			.option(
+				StringBuilder::new("period", "Which sprints to count (defaults to all-time)")
+					.choices(vec![
+						("Past week", "week"),
+						("Past month", "month"),
+						("Past year", "year"),
+						("All-time", "all"),
+					])
+			)
+	)
+	.option(export::subcommand())
+	.option(leaderboard::subcommand())
+	.option(notify::subcommand())
+	.option(schedule::subcommand_group())
 	.validate()
 	.into_diagnostic()
 	.map(|cmd| cmd.build())
@@ -90,24 +211,41 @@ pub async fn on_command(
 	interaction: &Interaction,
 	command_data: &CommandData,
 ) -> Result<()> {
-	let subcmd = command_data.options.iter().find_map(|opt| {
-		if let CommandOptionValue::SubCommand(ref sub) = opt.value {
+	let subcmd = command_data.options.iter().find_map(|opt| match opt.value {
+		CommandOptionValue::SubCommand(ref sub) | CommandOptionValue::SubCommandGroup(ref sub) => {
 			Some((opt.name.as_str(), sub.as_slice()))
-		} else {
-			None
 		}
+		_ => None,
 	});
 
 	match subcmd {
 		Some(("new", opts)) => sprint_new(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: new")?,
+		Some(("edit", opts)) => sprint_edit(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: edit")?,
 		Some(("list", opts)) => sprint_list(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: list")?,
 		Some(("summary", opts)) => sprint_summary(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: summary")?,
+		Some(("stats", opts)) => sprint_stats(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: stats")?,
+		Some(("export", opts)) => export::on_command(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: export")?,
+		Some(("leaderboard", opts)) => leaderboard::on_command(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: leaderboard")?,
+		Some(("notify", opts)) => notify::on_command(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: notify")?,
+		Some(("schedule", opts)) => schedule::on_command(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: schedule")?,
 		Some((other, _)) => warn!("unhandled sprint subcommand: {other}"),
 		_ => error!("unreachable bare sprint command"),
 	}
@@ -127,18 +265,42 @@ pub async fn on_component(
 		["join", uuid] => sprint_join(app.clone(), interaction, uuid)
 			.await
 			.wrap_err("action: join")?,
+		["join-team", uuid] => sprint_join_team(app.clone(), interaction, uuid, component_data)
+			.await
+			.wrap_err("action: join team")?,
 		["leave", uuid] => sprint_leave(app.clone(), interaction, uuid)
 			.await
 			.wrap_err("action: leave")?,
 		["cancel", uuid] => sprint_cancel(app.clone(), interaction, uuid)
 			.await
 			.wrap_err("action: cancel")?,
+		["delay", uuid] => sprint_delay(app.clone(), interaction, uuid)
+			.await
+			.wrap_err("action: delay")?,
+		["edit", uuid] => sprint_edit_modal(app.clone(), interaction, uuid)
+			.await
+			.wrap_err("action: edit modal")?,
+		["cancel-chain", uuid] => sprint_cancel_chain(app.clone(), interaction, uuid)
+			.await
+			.wrap_err("action: cancel chain")?,
 		["start-words", uuid] => sprint_words_start(app.clone(), interaction, uuid)
 			.await
 			.wrap_err("action: words modal: start")?,
 		["end-words", uuid] => sprint_words_end(app.clone(), interaction, uuid)
 			.await
 			.wrap_err("action: words modal: end")?,
+		["host-tools", uuid] => host::host_tools(app.clone(), interaction, uuid)
+			.await
+			.wrap_err("action: host tools")?,
+		["transfer-host", uuid] => host::transfer(app.clone(), interaction, uuid, component_data)
+			.await
+			.wrap_err("action: transfer host")?,
+		["kick", uuid] => host::kick(app.clone(), interaction, uuid, component_data)
+			.await
+			.wrap_err("action: kick")?,
+		["summarize", uuid] => sprint_summarize_now(app.clone(), interaction, uuid)
+			.await
+			.wrap_err("action: summarize now")?,
 		["save-words", sprint_id, project_id] => {
 			save_words(app.clone(), interaction, sprint_id, project_id)
 				.await
@@ -176,23 +338,164 @@ pub async fn on_modal(
 				.await
 				.wrap_err("action: words modal: ending")?
 		}
+		["edit", uuid] => sprint_edit_submit(app.clone(), interaction, uuid, component_data)
+			.await
+			.wrap_err("action: edit modal: submit")?,
+		["join", uuid] => sprint_join_submit(app.clone(), interaction, uuid, None, component_data)
+			.await
+			.wrap_err("action: join modal: submit")?,
+		["join", uuid, team] => {
+			sprint_join_submit(app.clone(), interaction, uuid, Some(team), component_data)
+				.await
+				.wrap_err("action: join modal: submit in team")?
+		}
 		id => warn!(?id, "unhandled sprint modal action"),
 	}
 
 	Ok(())
 }
 
+/// Set up the warning and start timers for a sprint that hasn't started yet.
+pub async fn schedule_start_timers(app: App, sprint: &Sprint) -> Result<()> {
+	schedule_all_reminders(app.clone(), sprint).await?;
+
+	if let Some(before) = sprint.guild_settings(app.clone()).await?.sprint_warning() {
+		let warning_in = sprint.warning_in(before);
+		if !warning_in.is_zero() {
+			debug!(?warning_in, "set up sprint warn timer");
+			app.send_timer(
+				Timer::new_after(
+					// UNWRAP: warning_in uses saturating_sub, will never be negative
+					warning_in.to_std().unwrap(),
+					SprintStartWarning::new(sprint),
+				)?
+				.with_key(sprint.timer_key("start-warning")),
+			)
+			.await?;
+		}
+	}
+
+	let starting_in = sprint.starting_in();
+	debug!(?starting_in, "set up sprint start timer");
+	app.send_timer(
+		Timer::new_after(
+			starting_in.positive_or(Duration::zero()).to_std().unwrap(),
+			SprintStart::new(sprint),
+		)?
+		.with_key(sprint.timer_key("start")),
+	)
+	.await
+}
+
+/// Set up the end warning and end timers for a sprint that has started.
+pub async fn schedule_end_timers(app: App, sprint: &Sprint) -> Result<()> {
+	let ending_in = sprint
+		.ending_in()
+		.to_std()
+		.map_err(|_| miette!("sprint ended before it began???"))?;
+
+	debug!("set up sprint end timer");
+	app.send_timer(
+		Timer::new_after(ending_in, SprintEnd::new(sprint))?.with_key(sprint.timer_key("end")),
+	)
+	.await?;
+
+	schedule_all_reminders(app.clone(), sprint).await?;
+
+	let Some(before) = sprint.guild_settings(app.clone()).await?.sprint_warning() else {
+		return Ok(());
+	};
+
+	debug!("set up sprint end warning timer");
+	app.send_timer(
+		Timer::new_after(
+			// UNWRAP: sprint_warning is only ever positive
+			ending_in.saturating_sub(before.to_std().unwrap()),
+			SprintEndWarning::new(sprint),
+		)?
+		.with_key(sprint.timer_key("end-warning")),
+	)
+	.await
+}
+
+/// How long after the end of a sprint participants are reminded to give their ending words.
+const END_REMINDER_DELAY: Duration = Duration::minutes(2);
+
+async fn schedule_all_reminders(app: App, sprint: &Sprint) -> Result<()> {
+	for participant in sprint.participants(app.clone()).await? {
+		schedule_reminders(app.clone(), sprint, participant.member).await?;
+	}
+
+	Ok(())
+}
+
+/// Set up reminder DMs for a participant, if they want them.
+///
+/// Reminders are keyed per participant, so this can be called again when the sprint changes.
+pub async fn schedule_reminders(app: App, sprint: &Sprint, member: Member) -> Result<()> {
+	let prefs = UserPreference::get_or_create(app.clone(), member).await?;
+	if !prefs.sprint_reminders {
+		return Ok(());
+	}
+
+	let remind_at = sprint.starting_at - Duration::minutes(prefs.reminder_minutes.into());
+	if sprint.status < SprintStatus::Started && remind_at > Utc::now() {
+		debug!(?member, %remind_at, "set up sprint start reminder");
+		app.send_timer(
+			Timer::new_at(
+				remind_at,
+				SprintReminder::new(sprint, member, ReminderKind::Start),
+			)
+			.with_key(SprintReminder::timer_key(
+				sprint,
+				member,
+				ReminderKind::Start,
+			)),
+		)
+		.await?;
+	}
+
+	let remind_at = sprint.ending_at() + END_REMINDER_DELAY;
+	debug!(?member, %remind_at, "set up sprint end reminder");
+	app.send_timer(
+		Timer::new_at(
+			remind_at,
+			SprintReminder::new(sprint, member, ReminderKind::End),
+		)
+		.with_key(SprintReminder::timer_key(sprint, member, ReminderKind::End)),
+	)
+	.await
+}
+
+/// Catch up on sprints whose timers may have been lost.
+///
+/// Timers are persisted, so this is mostly a safety net: everything it sets up replaces any
+/// pending timer with the same key instead of running twice.
 pub async fn load_from_db(app: App) -> Result<()> {
+	let now = Utc::now();
 	let finished = Sprint::get_all_finished_but_not_summaried(app.clone()).await?;
 	let mut need_summarying = 0;
+	let mut closing_out = 0;
 	for sprint in finished {
 		if sprint
 			.all_participants_have_ending_words(app.clone())
 			.await?
 		{
 			need_summarying += 1;
-			app.do_action(SprintSummary::new_from_db(app.clone(), sprint).await?)
-				.await?;
+			let key = sprint.timer_key("summary");
+			app.send_timer(
+				Timer::new_at(now, SprintSummary::new_from_db(app.clone(), sprint).await?)
+					.with_key(key),
+			)
+			.await?;
+		} else if sprint.status == SprintStatus::Ended {
+			closing_out += 1;
+			let grace = sprint.guild_settings(app.clone()).await?.summary_grace();
+			app.send_timer(
+				Timer::new_at(sprint.ending_at() + grace, SprintCloseOut::new(&sprint))
+					.with_key(sprint.timer_key("close-out")),
+			)
+			.await?;
 		}
 	}
 
@@ -200,7 +503,10 @@ pub async fn load_from_db(app: App) -> Result<()> {
 	let mut ended_late = 0;
 	for sprint in ended_but_we_are_late {
 		ended_late += 1;
-		app.do_action(SprintEnd::new(&sprint)).await?;
+		app.send_timer(
+			Timer::new_at(now, SprintEnd::new(&sprint)).with_key(sprint.timer_key("end")),
+		)
+		.await?;
 	}
 
 	let current = Sprint::get_all_current(app.clone()).await?;
@@ -215,53 +521,48 @@ pub async fn load_from_db(app: App) -> Result<()> {
 					.await?;
 			}
 			SprintStatus::Announced => {
-				// UNWRAP: warning_in uses saturating_sub, will never be negative
-				let warning_in = sprint.warning_in().to_std().unwrap();
-				if warning_in.is_zero() {
+				let missed_warning = sprint
+					.guild_settings(app.clone())
+					.await?
+					.sprint_warning()
+					.is_some_and(|before| sprint.warning_in(before).is_zero());
+				if missed_warning {
 					let starting_in = sprint.starting_in();
 					if starting_in > Duration::seconds(2) {
 						actioned_late += 1;
-						app.do_action(SprintStartWarning::new(&sprint)).await?;
-					} else if let Ok(starting_in) = starting_in.to_std() {
-						// implicitly checks that starting_in >= zero
-						rescheduled += 1;
-						app.send_timer(Timer::new_after(starting_in, SprintStart::new(&sprint))?)
-							.await?;
+						app.send_timer(
+							Timer::new_at(now, SprintStartWarning::new(&sprint))
+								.with_key(sprint.timer_key("start-warning")),
+						)
+						.await?;
 					} else {
-						actioned_late += 1;
-						app.do_action(SprintStart::new(&sprint)).await?;
+						rescheduled += 1;
 					}
+					schedule_start_timers(app.clone(), &sprint).await?;
 				} else {
 					rescheduled += 1;
-					app.send_timer(Timer::new_after(
-						warning_in,
-						SprintStartWarning::new(&sprint),
-					)?)
-					.await?;
+					schedule_start_timers(app.clone(), &sprint).await?;
 				}
 			}
 			SprintStatus::Started => {
-				if let Ok(ending_in) = sprint.ending_in().to_std() {
-					// implicitly checks that ending_in >= zero
+				if sprint.ending_in() >= Duration::zero() {
 					rescheduled += 1;
-					app.send_timer(Timer::new_after(ending_in, SprintEnd::new(&sprint))?)
-						.await?;
-					app.send_timer(Timer::new_after(
-						ending_in.saturating_sub(std::time::Duration::from_secs(30)),
-						SprintEndWarning::new(&sprint),
-					)?)
-					.await?;
+					schedule_end_timers(app.clone(), &sprint).await?;
 				} else {
 					warn!("sprint in init loaded from sprints_current that is started but is beyond end");
 					actioned_late += 1;
-					app.do_action(SprintEnd::new(&sprint)).await?;
+					app.send_timer(
+						Timer::new_at(now, SprintEnd::new(&sprint))
+							.with_key(sprint.timer_key("end")),
+					)
+					.await?;
 				}
 			}
 			_ => warn!(?sprint, "unhandled case of sprint loaded from db"),
 		}
 	}
 
-	info!(%actioned_late, %rescheduled, %ended_late, %need_summarying, "loaded sprints from db");
+	info!(%actioned_late, %rescheduled, %ended_late, %need_summarying, %closing_out, "loaded sprints from db");
 
 	Ok(())
 }
@@ -271,22 +572,136 @@ async fn sprint_new(
 	interaction: &Interaction,
 	options: &[CommandDataOption],
 ) -> Result<()> {
-	let duration = get_integer(options, "duration").unwrap_or(20);
-	if duration <= 0 {
-		return Err(miette!("duration must be positive"));
-	}
-	let duration = Duration::minutes(duration);
-
 	let channel = Channel::try_from(interaction)?;
 	let member = Member::try_from(interaction)?;
-	app.do_action(CommandAck::new(interaction)).await.log().ok();
+	let settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+
+	let duration = sprint_duration(
+		get_integer(options, "duration").unwrap_or(settings.sprint_duration.into()),
+	)?;
+
+	let rounds = get_integer(options, "rounds").unwrap_or(1);
+	let break_duration = get_integer(options, "break").unwrap_or(5);
+	if rounds <= 0 || break_duration <= 0 {
+		return Err(miette!("rounds and break must be positive"));
+	}
+
+	let teams = get_string(options, "teams").map(parse_teams).transpose()?;
+	let measure = match get_string(options, "measure").unwrap_or("word") {
+		"word" => SprintMeasure::Word,
+		"page" => SprintMeasure::Page,
+		"chapter" => SprintMeasure::Chapter,
+		"scene" => SprintMeasure::Scene,
+		"line" => SprintMeasure::Line,
+		other => return Err(miette!("unknown measure: {other}")),
+	};
+
+	let private = get_boolean(options, "private").unwrap_or(false);
+	if private && teams.is_some() {
+		return Err(miette!("a private sprint can't have teams"));
+	}
+
+	app.do_action(if private {
+		CommandAck::ephemeral(interaction)
+	} else {
+		CommandAck::new(interaction)
+	})
+	.await
+	.log()
+	.ok();
+
+	let starting = parse_starting_at(
+		app.clone(),
+		member,
+		get_string(options, "when").unwrap_or(&settings.sprint_when),
+	)
+	.await?;
+
+	debug!(%starting, %duration, ?channel, ?member, "recording sprint");
+	let mut sprint = Sprint::create(
+		app.clone(),
+		starting,
+		duration,
+		Some(&interaction.token),
+		channel,
+		Some(member),
+	)
+	.await?;
+
+	if rounds > 1 {
+		let chain = SprintChain::create(
+			app.clone(),
+			rounds.try_into().into_diagnostic()?,
+			Duration::minutes(break_duration),
+		)
+		.await?;
+		sprint.set_chain(app.clone(), chain.id, 1).await?;
+	}
+
+	if measure != SprintMeasure::Word {
+		sprint.set_measure(app.clone(), measure).await?;
+	}
+
+	if private {
+		sprint.set_private(app.clone()).await?;
+	}
+
+	if let Some(teams) = teams {
+		sprint.join_team(app.clone(), member, &teams[0]).await?;
+		sprint.set_teams(app.clone(), teams).await?;
+	}
+
+	sprint.carry_words(app.clone(), member).await?;
+
+	app.do_action(
+		SprintAnnounce::new(app.clone(), interaction, sprint)
+			.await
+			.wrap_err("rendering announce")?,
+	)
+	.await?;
+
+	Ok(())
+}
+
+/// Parse a comma-separated list of team names.
+fn parse_teams(teams: &str) -> Result<Vec<String>> {
+	let teams: Vec<String> = teams
+		.split(',')
+		.map(|team| team.trim().to_string())
+		.filter(|team| !team.is_empty())
+		.collect();
+
+	if teams.len() < 2 {
+		return Err(miette!("a team sprint needs at least two teams"));
+	}
+	// one select menu option per team
+	if teams.len() > 25 {
+		return Err(miette!("a team sprint can have at most 25 teams"));
+	}
+	if let Some(team) = teams.iter().find(|team| team.chars().count() > 100) {
+		return Err(miette!("team name is too long: {team}"));
+	}
+	if teams
+		.iter()
+		.map(|team| team.to_lowercase())
+		.unique()
+		.count()
+		!= teams.len()
+	{
+		return Err(miette!("team names must be different"));
+	}
+
+	Ok(teams)
+}
 
+/// Resolve a `when` option to the next time it refers to, in the member's timezone.
+async fn parse_starting_at(app: App, member: Member, when: &str) -> Result<DateTime<Utc>> {
 	// Get user's timezone preference, defaulting to Pacific/Auckland
-	let prefs = UserPreference::get_or_create(app.clone(), member).await?;
+	let prefs = UserPreference::get_or_create(app, member).await?;
 	let user_tz = prefs.timezone_tz()?;
 	let now = Utc::now().with_timezone(&user_tz);
 
-	let when = parse_when_relative_to(now.time(), get_string(options, "when").unwrap_or("15m"))?;
+	let when = parse_when_relative_to(now.time(), when)?;
 
 	let now_with_time = now.date().and_time(when).ok_or(miette!("invalid time"))?;
 	let starting = if now_with_time < now {
@@ -298,21 +713,64 @@ async fn sprint_new(
 		now_with_time
 	};
 
-	debug!(%starting, %duration, ?channel, ?member, "recording sprint");
-	let sprint =
-		Sprint::create(app.clone(), starting, duration, &interaction.token, member).await?;
+	Ok(starting.with_timezone(&Utc))
+}
 
-	app.do_action(
-		SprintAnnounce::new(app.clone(), interaction, sprint)
-			.await
-			.wrap_err("rendering announce")?,
-	)
-	.await?;
+async fn sprint_join(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
 
-	Ok(())
+	if sprint.status >= SprintStatus::Ended {
+		return Err(miette!("sprint has already ended"));
+	}
+	if sprint.private {
+		return Err(miette!("that sprint is private"));
+	}
+
+	app.do_action(SprintJoinModal::new(interaction, &sprint, None))
+		.await
 }
 
-async fn sprint_join(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+async fn sprint_join_team(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	component_data: &MessageComponentInteractionData,
+) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
+
+	if sprint.status >= SprintStatus::Ended {
+		return Err(miette!("sprint has already ended"));
+	}
+
+	let team = component_data
+		.values
+		.first()
+		.and_then(|value| usize::from_str(value).ok())
+		.filter(|index| {
+			sprint
+				.teams
+				.as_ref()
+				.is_some_and(|teams| *index < teams.len())
+		})
+		.ok_or_else(|| miette!("no such team in this sprint"))?;
+
+	app.do_action(SprintJoinModal::new(interaction, &sprint, Some(team)))
+		.await
+}
+
+async fn sprint_join_submit(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	team: Option<&str>,
+	data: &ModalInteractionData,
+) -> Result<()> {
 	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
 	let member = Member::try_from(interaction)?;
 	let sprint = Sprint::get_current(app.clone(), uuid)
@@ -323,15 +781,49 @@ async fn sprint_join(app: App, interaction: &Interaction, uuid: &str) -> Result<
 		return Err(miette!("sprint has already ended"));
 	}
 
+	let team = team
+		.map(|index| {
+			usize::from_str(index)
+				.ok()
+				.and_then(|index| sprint.teams.as_ref()?.get(index))
+				.ok_or_else(|| miette!("no such team in this sprint"))
+		})
+		.transpose()?;
+
+	let goal = data
+		.components
+		.iter()
+		.flat_map(|row| row.components.iter())
+		.find(|component| component.custom_id == "goal")
+		.and_then(|component| component.value.as_deref())
+		.map(str::trim)
+		.filter(|goal| !goal.is_empty())
+		.map(|goal| i32::from_str(goal).into_diagnostic())
+		.transpose()
+		.wrap_err("goal must be a number of words")?;
+	if goal.is_some_and(|goal| goal <= 0) {
+		return Err(miette!("goal must be a positive number of words"));
+	}
+
 	app.do_action(ComponentAck::ephemeral(interaction))
 		.await
 		.log()
 		.ok();
 
-	sprint.join(app.clone(), member).await?;
+	match team {
+		Some(team) => sprint.join_team(app.clone(), member, team).await?,
+		None => sprint.join(app.clone(), member).await?,
+	}
+	sprint.set_goal(app.clone(), member, goal).await?;
+	sprint.carry_words(app.clone(), member).await?;
+	schedule_reminders(app.clone(), &sprint, member).await?;
 
-	app.do_action(SprintJoined::new(interaction, &sprint)?)
-		.await?;
+	app.do_action(SprintJoined::new(
+		interaction,
+		&sprint,
+		team.map(String::as_str),
+	)?)
+	.await?;
 
 	app.do_action(SprintUpdate::new(&sprint)).await?;
 
@@ -356,11 +848,20 @@ async fn sprint_leave(app: App, interaction: &Interaction, uuid: &str) -> Result
 		.ok();
 
 	sprint.leave(app.clone(), member).await?;
+	cancel_reminders(app.clone(), &sprint, member).await?;
 
 	app.do_action(SprintLeft::new(interaction, &sprint)?)
 		.await?;
 
-	if sprint.participants(app.clone()).await?.is_empty() {
+	let participants = sprint.participants(app.clone()).await?;
+	if sprint.is_host(member) {
+		// whoever's been there the longest takes over
+		if let Some(next) = participants.iter().min_by_key(|p| p.joined_at) {
+			sprint.set_host(app.clone(), next.member).await?;
+		}
+	}
+
+	if participants.is_empty() {
 		let user = interaction
 			.member
 			.as_ref()
@@ -377,11 +878,22 @@ async fn sprint_leave(app: App, interaction: &Interaction, uuid: &str) -> Result
 	Ok(())
 }
 
+async fn cancel_reminders(app: App, sprint: &Sprint, member: Member) -> Result<()> {
+	for kind in [ReminderKind::Start, ReminderKind::End] {
+		app.cancel_timer(&SprintReminder::timer_key(sprint, member, kind))
+			.await?;
+	}
+
+	Ok(())
+}
+
 async fn sprint_cancel(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
 	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
 	let sprint = Sprint::get_current(app.clone(), uuid)
 		.await
 		.wrap_err("that sprint isn't current")?;
+	let member = Member::try_from(interaction)?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
 
 	let user = interaction
 		.member
@@ -406,12 +918,248 @@ async fn sprint_cancel(app: App, interaction: &Interaction, uuid: &str) -> Resul
 	Ok(())
 }
 
-async fn sprint_words_start(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+async fn sprint_cancel_chain(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let chain = SprintChain::get(app.clone(), uuid)
+		.await
+		.wrap_err("sprint chain not found")?;
+
+	// any round's host can cancel the whole chain
+	let member = Member::try_from(interaction)?;
+	let sprints = Sprint::get_all_in_chain(app.clone(), chain.id).await?;
+	if !sprints.iter().any(|sprint| sprint.is_host(member)) {
+		let Some(sprint) = sprints.first() else {
+			return Err(miette!("sprint chain has no sprints"));
+		};
+		require_host(app.clone(), sprint, interaction, member).await?;
+	}
+
+	let user = interaction
+		.member
+		.as_ref()
+		.and_then(|m| m.user.as_ref())
+		.ok_or(miette!("can only cancel sprint chain from a guild"))?;
+
+	if chain.is_cancelled() {
+		return Err(miette!("sprint chain was already cancelled"));
+	}
+
+	app.do_action(ComponentAck::new(interaction))
+		.await
+		.log()
+		.ok();
+
+	chain.cancel(app.clone()).await?;
+
+	app.do_action(SprintCancelled::new_chain(app.clone(), interaction, &chain, user).await?)
+		.await?;
+
+	Ok(())
+}
+
+async fn sprint_edit(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let shortid =
+		get_integer(options, "sprint").ok_or_else(|| miette!("sprint is a required field"))?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	let sprint = Sprint::get_from_shortid(
+		app.clone(),
+		member.guild_id,
+		shortid
+			.try_into()
+			.into_diagnostic()
+			.wrap_err("sprint ID is too large")?,
+	)
+	.await
+	.wrap_err("sprint not found")?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	let starting_at = match get_string(options, "when") {
+		Some(when) => Some(parse_starting_at(app.clone(), member, when).await?),
+		None => None,
+	};
+	let duration = get_integer(options, "duration")
+		.map(sprint_duration)
+		.transpose()?;
+
+	edit_sprint(app, interaction, sprint, starting_at, duration).await
+}
+
+async fn sprint_delay(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
 	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
 	let member = Member::try_from(interaction)?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	app.do_action(ComponentAck::new(interaction))
+		.await
+		.log()
+		.ok();
+
+	// once it's started, pushing it back means running for longer
+	let (starting_at, duration) = if sprint.status >= SprintStatus::Started {
+		(None, Some(sprint.duration() + Duration::minutes(5)))
+	} else {
+		(Some(sprint.starting_at + Duration::minutes(5)), None)
+	};
+
+	edit_sprint(app, interaction, sprint, starting_at, duration).await
+}
+
+async fn sprint_edit_modal(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
+	let member = Member::try_from(interaction)?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	if sprint.status >= SprintStatus::Ended {
+		return Err(miette!("sprint has already ended"));
+	}
+
+	app.do_action(SprintEditModal::new(interaction, &sprint))
+		.await
+}
+
+async fn sprint_edit_submit(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	data: &ModalInteractionData,
+) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let member = Member::try_from(interaction)?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	let field = |name: &str| {
+		data.components
+			.iter()
+			.flat_map(|row| row.components.iter())
+			.find(|component| component.custom_id == name)
+			.and_then(|component| component.value.as_deref())
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+	};
+
+	let duration = field("duration")
+		.map(|minutes| i64::from_str(minutes).into_diagnostic())
+		.transpose()
+		.wrap_err("duration must be a number of minutes")?
+		.map(sprint_duration)
+		.transpose()?;
+
+	app.do_action(ComponentAck::new(interaction))
+		.await
+		.log()
+		.ok();
+
+	let starting_at = match field("when") {
+		Some(when) => Some(parse_starting_at(app.clone(), member, when).await?),
+		None => None,
+	};
+
+	edit_sprint(app, interaction, sprint, starting_at, duration).await
+}
+
+/// Change a sprint's start and/or duration, and let everyone know.
+async fn edit_sprint(
+	app: App,
+	interaction: &Interaction,
+	sprint: Sprint,
+	starting_at: Option<DateTime<Utc>>,
+	duration: Option<Duration>,
+) -> Result<()> {
+	let user = interaction
+		.member
+		.as_ref()
+		.and_then(|m| m.user.as_ref())
+		.ok_or(miette!("can only edit sprint from a guild"))?;
+
+	if sprint.is_cancelled() {
+		return Err(miette!("sprint was cancelled"));
+	}
+	if sprint.status >= SprintStatus::Ended {
+		return Err(miette!("sprint has already ended"));
+	}
+
+	let started = sprint.status >= SprintStatus::Started;
+	if started && starting_at.is_some() {
+		return Err(miette!(
+			"sprint has already started, only its duration can be changed"
+		));
+	}
+
+	let starting_at = starting_at.unwrap_or(sprint.starting_at);
+	let duration = duration.unwrap_or_else(|| sprint.duration());
+	if duration <= Duration::zero() {
+		return Err(miette!("duration must be positive"));
+	}
+	if started && starting_at + duration <= Utc::now() {
+		return Err(miette!("that would end the sprint in the past"));
+	}
+
+	debug!(?sprint.id, %starting_at, %duration, "editing sprint");
+	let sprint = sprint
+		.reschedule(app.clone(), starting_at, duration)
+		.await?;
+
+	if started {
+		schedule_end_timers(app.clone(), &sprint).await?;
+	} else {
+		schedule_start_timers(app.clone(), &sprint).await?;
+	}
+
+	app.do_action(SprintUpdate::new(&sprint)).await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(format!(
+				"✏️ Sprint `{shortid}` was changed by {user}: it starts at {starting_at_ts} and lasts for {duration}.",
+				shortid = sprint.shortid,
+				user = user.id.mention(),
+				starting_at_ts = sprint.starting_at.discord_format('T'),
+				duration = sprint.formatted_duration(),
+			)),
+			ephemeral: sprint.private,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+/// The member behind an interaction about the sprint.
+///
+/// Messages about private sprints can end up in the host's DMs, where there's no guild to tell
+/// which member they are, so it comes from the sprint instead.
+fn sprint_member(interaction: &Interaction, sprint: &Sprint) -> Result<Member> {
+	match (Member::try_from(interaction), sprint.host) {
+		(Ok(member), _) => Ok(member),
+		(Err(_), Some(host)) if sprint.private && interaction.author_id() == Some(host.into()) => {
+			Ok(host)
+		}
+		(Err(err), _) => Err(err),
+	}
+}
+
+async fn sprint_words_start(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
 	let sprint = Sprint::get(app.clone(), uuid)
 		.await
 		.wrap_err("sprint not found")?;
+	let member = sprint_member(interaction, &sprint)?;
 
 	if sprint.is_cancelled() {
 		return Err(miette!("sprint was cancelled"));
@@ -425,10 +1173,10 @@ async fn sprint_words_start(app: App, interaction: &Interaction, uuid: &str) ->
 
 async fn sprint_words_end(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
 	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
-	let member = Member::try_from(interaction)?;
 	let sprint = Sprint::get(app.clone(), uuid)
 		.await
 		.wrap_err("sprint not found")?;
+	let member = sprint_member(interaction, &sprint)?;
 
 	if sprint.is_cancelled() {
 		return Err(miette!("sprint was cancelled"));
@@ -440,6 +1188,32 @@ async fn sprint_words_end(app: App, interaction: &Interaction, uuid: &str) -> Re
 	Ok(())
 }
 
+/// Summarize an ended sprint without waiting for the rest of the ending words.
+async fn sprint_summarize_now(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let sprint = Sprint::get(app.clone(), uuid)
+		.await
+		.wrap_err("sprint not found")?;
+	let member = sprint_member(interaction, &sprint)?;
+
+	if sprint.is_cancelled() {
+		return Err(miette!("sprint was cancelled"));
+	}
+	if sprint.status >= SprintStatus::Summaried {
+		return Err(miette!("sprint was already summarized"));
+	}
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	app.do_action(ComponentAck::new(interaction))
+		.await
+		.log()
+		.ok();
+
+	app.cancel_timer(&sprint.timer_key("close-out")).await?;
+	app.do_action(SprintSummary::new(app.clone(), interaction, sprint).await?)
+		.await
+}
+
 async fn sprint_set_words(
 	app: App,
 	interaction: &Interaction,
@@ -448,10 +1222,10 @@ async fn sprint_set_words(
 	column: &str,
 ) -> Result<()> {
 	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
-	let member = Member::try_from(interaction)?;
 	let sprint = Sprint::get(app.clone(), uuid)
 		.await
 		.wrap_err("sprint not found")?;
+	let member = sprint_member(interaction, &sprint)?;
 
 	if sprint.is_cancelled() {
 		return Err(miette!("sprint was cancelled"));
@@ -491,10 +1265,14 @@ async fn sprint_set_words(
 			.await?
 		{
 			// Delay so that it hopefully doesn't inherit the ephemeralness
-			app.send_timer(Timer::new_after(
-				StdDuration::from_secs(1),
-				SprintSummary::new(app.clone(), interaction, sprint).await?,
-			)?)
+			let key = sprint.timer_key("summary");
+			app.send_timer(
+				Timer::new_after(
+					StdDuration::from_secs(1),
+					SprintSummary::new(app.clone(), interaction, sprint).await?,
+				)?
+				.with_key(key),
+			)
 			.await?;
 		}
 	}
@@ -505,17 +1283,38 @@ async fn sprint_set_words(
 async fn sprint_list(
 	app: App,
 	interaction: &Interaction,
-	_options: &[CommandDataOption],
+	options: &[CommandDataOption],
 ) -> Result<()> {
-	let sprints = Sprint::get_all_current(app.clone()).await?;
+	let here = get_boolean(options, "here").unwrap_or(false);
+	let channel = Channel::try_from(interaction)?;
+	let member = Member::try_from(interaction)?;
+	let mut sprints = if here {
+		Sprint::get_all_current_in_channel(app.clone(), channel).await?
+	} else {
+		let guild_id = channel
+			.guild_id
+			.ok_or_else(|| miette!("can only list sprints in a server"))?;
+		Sprint::get_all_current_in_guild(app.clone(), guild_id).await?
+	};
+	sprints.retain(|sprint| !sprint.private || sprint.is_host(member));
 	app.do_action(CommandAck::new(interaction)).await.log().ok();
 
 	let content = if sprints.is_empty() {
-		"No sprints are currently running.".to_string()
+		if here {
+			"No sprints are currently running in this channel.".to_string()
+		} else {
+			"No sprints are currently running.".to_string()
+		}
 	} else {
 		try_join_all(sprints.into_iter().map(|sprint| {
 			let app = app.clone();
-			async move { sprint.status_text(app, false).await }
+			async move {
+				let text = sprint.status_text(app, false).await?;
+				Ok::<_, miette::Report>(match sprint.channel {
+					Some(chan) if !here => format!("{text} (in {})", chan.mention()),
+					_ => text,
+				})
+			}
 		}))
 		.await?
 		.join("\n")
@@ -541,10 +1340,12 @@ async fn sprint_summary(
 	let shortid =
 		get_integer(options, "sprint").ok_or_else(|| miette!("sprint is a required field"))?;
 	debug!(?shortid, "got shortid");
+	let member = Member::try_from(interaction)?;
 	app.do_action(CommandAck::new(interaction)).await.log().ok();
 
 	let sprint = Sprint::get_from_shortid(
 		app.clone(),
+		member.guild_id,
 		shortid
 			.try_into()
 			.into_diagnostic()
@@ -552,9 +1353,94 @@ async fn sprint_summary(
 	)
 	.await
 	.wrap_err("sprint not found")?;
+	if sprint.private && !sprint.is_host(member) {
+		return Err(miette!("that sprint is private"));
+	}
 
-	app.do_action(SprintSummary::new(app.clone(), interaction, sprint).await?)
-		.await
+	if get_boolean(options, "image").unwrap_or(false) {
+		app.do_action(SprintSummaryCard::new(app.clone(), interaction, sprint).await?)
+			.await
+	} else {
+		app.do_action(SprintSummary::new(app.clone(), interaction, sprint).await?)
+			.await
+	}
+}
+
+async fn sprint_stats(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let (since, period) = match get_string(options, "period").unwrap_or("all") {
+		"week" => (Some(Utc::now() - Duration::days(7)), "in the past week"),
+		"month" => (Some(Utc::now() - Duration::days(30)), "in the past month"),
+		"year" => (Some(Utc::now() - Duration::days(365)), "in the past year"),
+		"all" => (None, "all-time"),
+		other => return Err(miette!("unknown period: {other}")),
+	};
+
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	let stats = SprintStats::get(app.clone(), member, since).await?;
+	let content = if stats.sprints == 0 {
+		format!("{} hasn't finished any sprints {period}.", member.mention())
+	} else {
+		let recent = SprintHistoryEntry::get_recent(app.clone(), member, since, 5).await?;
+		let mut lines = vec![
+			format!("📊 Sprint stats for {} ({period}):", member.mention()),
+			format!(
+				"**{sprints}** sprints, **{words}** words",
+				sprints = stats.sprints,
+				words = stats.words
+			),
+		];
+		if let Some(wpm) = stats.average_wpm() {
+			lines.push(format!("Average: **{wpm:.1}** words per minute"));
+		}
+		if let Some(wpm) = stats.best_wpm {
+			lines.push(format!("Best: **{wpm:.1}** words per minute"));
+		}
+		if let Some(rate) = stats.goal_hit_rate() {
+			lines.push(format!(
+				"Goals: 🎯 met **{met}** of {set} (**{percent:.0}%**)",
+				met = stats.goals_met,
+				set = stats.goals_set,
+				percent = rate * 100.0,
+			));
+		}
+		if let Some(longest) = stats.longest() {
+			lines.push(format!(
+				"Longest sprint: {}",
+				format_duration(longest.round_to_seconds())
+			));
+		}
+
+		lines.push(String::new());
+		lines.push("Recent sprints:".into());
+		lines.extend(recent.iter().map(|entry| {
+			format!(
+				"`{shortid}` {when}: **{words}** words in {duration} ({wpm:.1} wpm)",
+				shortid = entry.shortid,
+				when = entry.starting_at.discord_format('d'),
+				words = entry.words,
+				duration = format_duration(entry.duration().round_to_seconds()),
+				wpm = entry.wpm(),
+			)
+		}));
+
+		lines.join("\n")
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
 }
 
 async fn save_words(
@@ -563,7 +1449,6 @@ async fn save_words(
 	sprint_id: &str,
 	project_id: &str,
 ) -> Result<()> {
-	let member = Member::try_from(interaction)?;
 	let sprint_id = Uuid::from_str(sprint_id).into_diagnostic()?;
 	let project_id = Uuid::from_str(project_id).into_diagnostic()?;
 	app.do_action(ComponentAck::new(interaction))
@@ -574,6 +1459,7 @@ async fn save_words(
 	let sprint = Sprint::get(app.clone(), sprint_id)
 		.await
 		.wrap_err("sprint not found")?;
+	let member = sprint_member(interaction, &sprint)?;
 	let participant = sprint.participant(app.clone(), member).await?;
 	let words = SaveWords::Relative(participant.words_written().unwrap_or(0).into());
 
@@ -582,11 +1468,22 @@ async fn save_words(
 		.and_then(|opt| opt.ok_or_else(|| miette!("no project for {:?}", member)))
 		.wrap_err("project not found")?;
 
-	let client = TrackbearLogin::client_for_member(app.clone(), member)
+	// not checking the key, so the words can be queued if TrackBear is down
+	let client = TrackbearLogin::get_for_member(app.clone(), member)
 		.await?
+		.and_then(|login| login.client_unchecked(app.clone()))
 		.ok_or_else(|| miette!("no trackbear login for {:?}", member))?;
 
-	save_words_action(app, interaction, &client, &project, words).await
+	save_words_action(
+		app,
+		interaction,
+		&client,
+		&project,
+		sprint.measure.into(),
+		words,
+		TallyDetails::default(),
+	)
+	.await
 }
 
 async fn save_never(app: App, interaction: &Interaction, login_id: &str) -> Result<()> {
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use miette::{miette, Result};
+use tracing::debug;
+use twilight_model::{
+	application::interaction::{application_command::CommandDataOption, Interaction},
+	http::attachment::Attachment,
+};
+use twilight_util::builder::command::{StringBuilder, SubCommandBuilder};
+
+use crate::{
+	bot::{
+		action::CommandAck,
+		context::{GenericResponse, GenericResponseData},
+		utils::command::get_string,
+		App,
+	},
+	db::{
+		export::{encode, ExportFilter, ExportFormat, ExportRow},
+		member::Member,
+	},
+	error_ext::ErrorExt,
+};
+
+/// Add the format and date range options to an export subcommand.
+pub fn with_options(subcommand: SubCommandBuilder) -> SubCommandBuilder {
+	subcommand
+		.option(
+			StringBuilder::new("format", "File format (defaults to CSV)")
+				.choices(vec![("CSV", "csv"), ("JSON", "json")]),
+		)
+		.option(StringBuilder::new(
+			"since",
+			"Only sprints on or after this date, like 2024-11-01",
+		))
+		.option(StringBuilder::new(
+			"until",
+			"Only sprints on or before this date, like 2024-11-30",
+		))
+}
+
+pub fn subcommand() -> SubCommandBuilder {
+	with_options(SubCommandBuilder::new(
+		"export",
+		"Download your sprints in this server as a file",
+	))
+}
+
+/// Export the member's own sprints in the guild, including their private ones.
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let member = Member::try_from(interaction)?;
+	let filter = ExportFilter {
+		guild_id: Some(member.guild_id),
+		user_id: Some(member.user_id),
+		include_private: true,
+		..Default::default()
+	};
+
+	send_export(app, interaction, options, filter, "my-sprints").await
+}
+
+/// Export everyone's sprints in the guild, for admins.
+pub async fn on_guild_command(
+	app: App,
+	interaction: &Interaction,
+	member: Member,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let filter = ExportFilter {
+		guild_id: Some(member.guild_id),
+		..Default::default()
+	};
+
+	send_export(app, interaction, options, filter, "server-sprints").await
+}
+
+async fn send_export(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+	filter: ExportFilter,
+	name: &str,
+) -> Result<()> {
+	let format = get_string(options, "format")
+		.map(ExportFormat::from_str)
+		.transpose()
+		.map_err(|err| miette!("{err}"))?
+		.unwrap_or_default();
+	let filter = filter.with_dates(get_string(options, "since"), get_string(options, "until"))?;
+
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let rows = ExportRow::query(&app.db, &filter).await?;
+	debug!(count = rows.len(), %format, "exporting sprints");
+
+	let content = match rows.len() {
+		0 => "There are no sprints to export for that range.".to_string(),
+		n => format!("📦 Exported {n} rows, one per sprint and participant."),
+	};
+	let attachments = if rows.is_empty() {
+		Vec::new()
+	} else {
+		vec![Attachment::from_bytes(
+			format!("{name}.{}", format.extension()),
+			encode(&rows, format)?,
+			0,
+		)]
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			attachments,
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use miette::{miette, Context, IntoDiagnostic, Result};
+use twilight_model::{
+	application::interaction::{message_component::MessageComponentInteractionData, Interaction},
+	channel::message::component::{Component, SelectMenu, SelectMenuOption, SelectMenuType},
+	guild::Permissions,
+};
+use uuid::Uuid;
+
+use crate::{
+	bot::{
+		action::{ComponentAck, SprintUpdate},
+		utils::{action_row, respond_ephemeral},
+		App,
+	},
+	db::{member::Member, sprint::Sprint},
+	error_ext::ErrorExt,
+};
+
+use super::cancel_reminders;
+
+/// Error unless the member is the sprint's host, or can manage messages in the channel.
+///
+/// Sprints from before hosts were recorded can be run by any of their participants.
+pub async fn require_host(
+	app: App,
+	sprint: &Sprint,
+	interaction: &Interaction,
+	member: Member,
+) -> Result<()> {
+	// Discord resolves these for the channel the interaction came from, overwrites and all
+	let is_moderator = interaction
+		.member
+		.as_ref()
+		.and_then(|m| m.permissions)
+		.is_some_and(|permissions| permissions.contains(Permissions::MANAGE_MESSAGES));
+
+	let allowed = is_moderator
+		|| match sprint.host {
+			Some(host) => host == member,
+			None => sprint
+				.participants(app)
+				.await?
+				.iter()
+				.any(|participant| participant.member == member),
+		};
+
+	if allowed {
+		Ok(())
+	} else {
+		Err(miette!(
+			"only the sprint's host (or a moderator) can do that"
+		))
+	}
+}
+
+/// Show the host the controls for handing over the sprint and kicking participants.
+pub async fn host_tools(app: App, interaction: &Interaction, uuid: &str) -> Result<()> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let member = Member::try_from(interaction)?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	app.do_action(ComponentAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let mut options = Vec::new();
+	for participant in sprint.participants(app.clone()).await? {
+		if sprint.is_host(participant.member) {
+			continue;
+		}
+
+		options.push(SelectMenuOption {
+			default: false,
+			description: None,
+			emoji: None,
+			label: participant.member.name(app.clone()).await?,
+			value: participant.member.user_id.to_string(),
+		});
+	}
+	// select menus can't have more than 25 options
+	options.truncate(25);
+
+	if options.is_empty() {
+		return respond_ephemeral(
+			app,
+			interaction,
+			"There's nobody else in this sprint yet.".to_string(),
+			Vec::new(),
+		)
+		.await;
+	}
+
+	let menu = |action: &str, placeholder: &str| {
+		action_row(vec![Component::SelectMenu(SelectMenu {
+			channel_types: None,
+			custom_id: format!("sprint:{action}:{}", sprint.id),
+			default_values: None,
+			disabled: false,
+			kind: SelectMenuType::Text,
+			max_values: Some(1),
+			min_values: Some(1),
+			options: Some(options.clone()),
+			placeholder: Some(placeholder.to_string()),
+		})])
+	};
+
+	let mut components = menu("transfer-host", "Hand over hosting to…");
+	components.extend(menu("kick", "Remove from the sprint…"));
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("👑 Host tools for sprint `{}`", sprint.shortid),
+		components,
+	)
+	.await
+}
+
+pub async fn transfer(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	component_data: &MessageComponentInteractionData,
+) -> Result<()> {
+	let (sprint, target) = selected(app.clone(), interaction, uuid, component_data).await?;
+
+	app.do_action(ComponentAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	sprint.set_host(app.clone(), target).await?;
+	app.do_action(SprintUpdate::new(&sprint)).await?;
+
+	respond_ephemeral(
+		app.clone(),
+		interaction,
+		format!(
+			"👑 {} is now hosting sprint `{}`.",
+			target.name(app).await?,
+			sprint.shortid
+		),
+		Vec::new(),
+	)
+	.await
+}
+
+pub async fn kick(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	component_data: &MessageComponentInteractionData,
+) -> Result<()> {
+	let (sprint, target) = selected(app.clone(), interaction, uuid, component_data).await?;
+	if sprint.is_host(target) {
+		return Err(miette!(
+			"the host can't be removed, hand over hosting first"
+		));
+	}
+
+	app.do_action(ComponentAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	sprint.leave(app.clone(), target).await?;
+	cancel_reminders(app.clone(), &sprint, target).await?;
+	app.do_action(SprintUpdate::new(&sprint)).await?;
+
+	respond_ephemeral(
+		app.clone(),
+		interaction,
+		format!(
+			"👋 Removed {} from sprint `{}`.",
+			target.name(app).await?,
+			sprint.shortid
+		),
+		Vec::new(),
+	)
+	.await
+}
+
+/// The sprint and the participant picked from one of the host tools menus.
+async fn selected(
+	app: App,
+	interaction: &Interaction,
+	uuid: &str,
+	component_data: &MessageComponentInteractionData,
+) -> Result<(Sprint, Member)> {
+	let uuid = Uuid::from_str(uuid).into_diagnostic()?;
+	let member = Member::try_from(interaction)?;
+	let sprint = Sprint::get_current(app.clone(), uuid)
+		.await
+		.wrap_err("that sprint isn't current")?;
+	require_host(app.clone(), &sprint, interaction, member).await?;
+
+	let target = component_data
+		.values
+		.first()
+		.and_then(|value| i64::from_str(value).ok())
+		.map(|user_id| Member {
+			guild_id: member.guild_id,
+			user_id,
+		})
+		.ok_or_else(|| miette!("no participant was picked"))?;
+
+	sprint
+		.participant(app, target)
+		.await
+		.wrap_err("they're not in this sprint anymore")?;
+
+	Ok((sprint, target))
+}
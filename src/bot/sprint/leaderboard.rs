@@ -0,0 +1,228 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use humantime::format_duration;
+use miette::{miette, IntoDiagnostic, Result};
+use tracing::debug;
+use twilight_model::application::interaction::{
+	application_command::CommandDataOption, Interaction,
+};
+use twilight_util::builder::command::{StringBuilder, SubCommandBuilder};
+
+use crate::{
+	bot::{
+		action::CommandAck,
+		context::{GenericResponse, GenericResponseData},
+		utils::{command::get_string, ranks, time::ChronoDurationExt},
+		App,
+	},
+	db::{member::Member, sprint_stats::LeaderboardEntry, user_preference::UserPreference},
+	error_ext::ErrorExt,
+};
+
+/// How many members are shown on a leaderboard.
+const LEADERBOARD_SIZE: usize = 10;
+
+pub fn subcommand() -> SubCommandBuilder {
+	SubCommandBuilder::new("leaderboard", "Show who sprinted the most in this server")
+		.option(
+			StringBuilder::new("period", "Which sprints to count (defaults to this week)").choices(
+				vec![
+					("Today", "today"),
+					("This week", "week"),
+					("This month", "month"),
+					("Custom dates (set from and to)", "custom"),
+				],
+			),
+		)
+		.option(
+			StringBuilder::new("metric", "What to rank by (defaults to total words)").choices(
+				vec![
+					("Total words", "words"),
+					("Best words per minute", "wpm"),
+					("Number of sprints", "sprints"),
+					("Time spent sprinting", "time"),
+				],
+			),
+		)
+		.option(StringBuilder::new(
+			"from",
+			"First day of a custom period, like 2024-11-01",
+		))
+		.option(StringBuilder::new(
+			"to",
+			"Last day of a custom period, like 2024-11-30 (defaults to today)",
+		))
+}
+
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let member = Member::try_from(interaction)?;
+	let tz = UserPreference::get_or_create(app.clone(), member)
+		.await?
+		.timezone_tz()?;
+
+	let (from, until, period) = period_range(tz, Utc::now(), options)?;
+	let metric = Metric::parse(get_string(options, "metric").unwrap_or("words"))?;
+
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	let mut entries =
+		LeaderboardEntry::get_all_in_guild(app.clone(), member.guild_id, from, until).await?;
+	entries.sort_by(|a, b| metric.value(b).total_cmp(&metric.value(a)));
+
+	let mut shown = Vec::with_capacity(LEADERBOARD_SIZE);
+	for entry in entries {
+		if shown.len() >= LEADERBOARD_SIZE {
+			break;
+		}
+
+		// members who left the server can't be named, so they're left off
+		match entry.member.name_if_present(app.clone()).await? {
+			Some(name) => shown.push((name, entry)),
+			None => debug!(?entry.member, "skipping member not in the server anymore"),
+		}
+	}
+
+	let content = if shown.is_empty() {
+		format!("Nobody finished a sprint {period}.")
+	} else {
+		let values: Vec<f64> = shown.iter().map(|(_, entry)| metric.value(entry)).collect();
+		let lines = shown
+			.iter()
+			.zip(ranks(&values))
+			.map(|((name, entry), rank)| {
+				format!(
+					"**{rank}.** _{name}_: {value}",
+					name = name.replace('_', "\\_"),
+					value = metric.format(entry),
+				)
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+		format!(
+			"🏆 Sprint leaderboard by {} {period}:\n{lines}",
+			metric.label()
+		)
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Metric {
+	Words,
+	BestWpm,
+	Sprints,
+	Time,
+}
+
+impl Metric {
+	fn parse(s: &str) -> Result<Self> {
+		match s {
+			"words" => Ok(Self::Words),
+			"wpm" => Ok(Self::BestWpm),
+			"sprints" => Ok(Self::Sprints),
+			"time" => Ok(Self::Time),
+			other => Err(miette!("unknown metric: {other}")),
+		}
+	}
+
+	fn label(self) -> &'static str {
+		match self {
+			Self::Words => "total words",
+			Self::BestWpm => "best words per minute",
+			Self::Sprints => "number of sprints",
+			Self::Time => "time spent sprinting",
+		}
+	}
+
+	fn value(self, entry: &LeaderboardEntry) -> f64 {
+		match self {
+			Self::Words => entry.words as f64,
+			Self::BestWpm => entry.best_wpm.unwrap_or(0.0),
+			Self::Sprints => entry.sprints as f64,
+			Self::Time => entry.minutes,
+		}
+	}
+
+	fn format(self, entry: &LeaderboardEntry) -> String {
+		match self {
+			Self::Words => format!("**{}** words", entry.words),
+			Self::BestWpm => format!("**{:.1}** words per minute", entry.best_wpm.unwrap_or(0.0)),
+			Self::Sprints => format!("**{}** sprints", entry.sprints),
+			Self::Time => format!(
+				"**{}**",
+				format_duration(
+					Duration::seconds((entry.minutes * 60.0) as i64).round_to_seconds()
+				)
+			),
+		}
+	}
+}
+
+/// The range of time to count sprints in, with a description of it.
+///
+/// Periods follow the calendar in the member's timezone; weeks start on Monday.
+fn period_range(
+	tz: Tz,
+	now: DateTime<Utc>,
+	options: &[CommandDataOption],
+) -> Result<(DateTime<Utc>, DateTime<Utc>, String)> {
+	let today = now.with_timezone(&tz).date_naive();
+	match get_string(options, "period").unwrap_or("week") {
+		"today" => Ok((local_midnight(tz, today)?, now, "today".into())),
+		"week" => {
+			let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+			Ok((local_midnight(tz, monday)?, now, "this week".into()))
+		}
+		"month" => {
+			// UNWRAP: every month has a first day
+			let first = today.with_day(1).unwrap();
+			Ok((local_midnight(tz, first)?, now, "this month".into()))
+		}
+		"custom" => {
+			let from = get_string(options, "from")
+				.ok_or_else(|| miette!("a custom period needs a from date"))
+				.and_then(parse_date)?;
+			let to = get_string(options, "to").map_or(Ok(today), parse_date)?;
+			if to < from {
+				return Err(miette!("the period can't end before it starts"));
+			}
+
+			let after = to
+				.succ_opt()
+				.ok_or_else(|| miette!("{to} is too far in the future"))?;
+
+			Ok((
+				local_midnight(tz, from)?,
+				local_midnight(tz, after)?,
+				format!("from {from} to {to}"),
+			))
+		}
+		other => Err(miette!("unknown period: {other}")),
+	}
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate> {
+	NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+		.into_diagnostic()
+		.map_err(|err| err.wrap_err(format!("{s:?} is not a date like 2024-11-01")))
+}
+
+fn local_midnight(tz: Tz, date: NaiveDate) -> Result<DateTime<Utc>> {
+	tz.from_local_datetime(&date.and_time(NaiveTime::MIN))
+		.earliest()
+		.map(|midnight| midnight.with_timezone(&Utc))
+		.ok_or_else(|| miette!("{date} has no midnight in {tz}"))
+}
@@ -0,0 +1,106 @@
+use chrono::Duration;
+use miette::{miette, Result};
+use tracing::{debug, warn};
+use twilight_mention::Mention;
+use twilight_model::application::interaction::{
+	application_command::CommandDataOption, Interaction,
+};
+use twilight_util::builder::command::{BooleanBuilder, SubCommandBuilder};
+
+use crate::{
+	bot::{
+		action::CommandAck,
+		context::{GenericResponse, GenericResponseData},
+		utils::{command::get_boolean, time::ChronoDateTimeExt},
+		App,
+	},
+	db::{
+		member::Member, message::Message, sprint::Sprint, sprint_notification::SprintNotification,
+	},
+	error_ext::ErrorExt,
+};
+
+/// How long after a notification a member won't get another one.
+const NOTIFY_COOLDOWN: Duration = Duration::hours(1);
+
+pub fn subcommand() -> SubCommandBuilder {
+	SubCommandBuilder::new(
+		"notify",
+		"Get a DM when a sprint is announced in this server",
+	)
+	.option(
+		BooleanBuilder::new(
+			"enabled",
+			"Whether to DM you about new sprints (at most hourly)",
+		)
+		.required(true),
+	)
+}
+
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let enabled =
+		get_boolean(options, "enabled").ok_or_else(|| miette!("enabled is a required field"))?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	if enabled {
+		SprintNotification::subscribe(app.clone(), member).await?;
+	} else {
+		SprintNotification::unsubscribe(app.clone(), member).await?;
+	}
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(if enabled {
+				"🔔 You'll get a DM when a sprint is announced in this server (at most once an hour)."
+					.to_string()
+			} else {
+				"🔕 You won't get DMs about sprints in this server anymore.".to_string()
+			}),
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+/// DM the guild's subscribers about a sprint which was just announced.
+///
+/// Members who can't be DMed are skipped, so one closed inbox doesn't stop the others.
+#[tracing::instrument(skip(app))]
+pub async fn notify_subscribers(app: App, sprint: &Sprint, announce: Message) -> Result<()> {
+	let Some(guild_id) = announce.channel.guild_id else {
+		return Ok(());
+	};
+
+	let members =
+		SprintNotification::claim_for_sprint(app.clone(), guild_id, sprint.id, NOTIFY_COOLDOWN)
+			.await?;
+	debug!(count = members.len(), "notifying subscribers of sprint");
+
+	let content = format!(
+		"🔔 Sprint `{shortid}` is starting {starting_in} in {channel}, for {duration}: {link}",
+		shortid = sprint.shortid,
+		starting_in = sprint.starting_at.discord_format('R'),
+		channel = announce.channel.mention(),
+		duration = sprint.formatted_duration(),
+		link = announce.link(),
+	);
+
+	for member in members {
+		if let Err(err) = app.send_dm(member, &content).await {
+			warn!(?member, ?err, "could not notify member of sprint");
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,358 @@
+use chrono::{DateTime, Duration, Utc};
+use miette::{miette, Context, IntoDiagnostic, Result};
+use tracing::{debug, info, warn};
+use twilight_mention::Mention;
+use twilight_model::{
+	application::interaction::{
+		application_command::{CommandDataOption, CommandOptionValue},
+		Interaction,
+	},
+	guild::Permissions,
+};
+use twilight_util::builder::command::{
+	IntegerBuilder, StringBuilder, SubCommandBuilder, SubCommandGroupBuilder,
+};
+
+use crate::{
+	bot::{
+		action::{CommandAck, SprintScheduleFire},
+		context::{GenericResponse, GenericResponseData, Timer},
+		utils::{
+			command::{get_integer, get_string},
+			recurrence::Recurrence,
+			time::{ChronoDateTimeExt, ChronoDurationExt},
+		},
+		App,
+	},
+	db::{
+		channel::Channel, guild_settings::GuildSettings, member::Member,
+		sprint_schedule::SprintSchedule, user_preference::UserPreference,
+	},
+	error_ext::ErrorExt,
+};
+
+/// How long before each sprint in a series it gets announced.
+const ANNOUNCE_AHEAD: Duration = Duration::minutes(10);
+
+pub fn subcommand_group() -> SubCommandGroupBuilder {
+	SubCommandGroupBuilder::new("schedule", "Recurring sprints in this channel").subcommands([
+		SubCommandBuilder::new("new", "Schedule a recurring series of sprints in this channel")
+			.option(
+				StringBuilder::new(
+					"days",
+					"Which days to run on: daily, weekdays, weekends, or a list like mon, wed, fri",
+				)
+				.required(true),
+			)
+			.option(
+				StringBuilder::new(
+					"times",
+					"Start times in your timezone, like 10:00, 14:30 or 18:00-22:00/30 for every half hour",
+				)
+				.required(true),
+			)
+			.option(IntegerBuilder::new(
+				"duration",
+				"Duration of each sprint in minutes (defaults to 20)",
+			)),
+		SubCommandBuilder::new("list", "List all sprint series in this server"),
+		SubCommandBuilder::new("pause", "Stop running a sprint series until it's resumed").option(
+			IntegerBuilder::new("series", "Short series ID, like 12").required(true),
+		),
+		SubCommandBuilder::new("resume", "Start running a paused sprint series again").option(
+			IntegerBuilder::new("series", "Short series ID, like 12").required(true),
+		),
+		SubCommandBuilder::new("delete", "Delete a sprint series").option(
+			IntegerBuilder::new("series", "Short series ID, like 12").required(true),
+		),
+	])
+}
+
+pub async fn on_command(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let subcmd = options.iter().find_map(|opt| {
+		if let CommandOptionValue::SubCommand(ref sub) = opt.value {
+			Some((opt.name.as_str(), sub.as_slice()))
+		} else {
+			None
+		}
+	});
+
+	match subcmd {
+		Some(("new", opts)) => schedule_new(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: schedule new")?,
+		Some(("list", _)) => schedule_list(app.clone(), interaction)
+			.await
+			.wrap_err("command: schedule list")?,
+		Some(("pause", opts)) => schedule_pause(app.clone(), interaction, opts, true)
+			.await
+			.wrap_err("command: schedule pause")?,
+		Some(("resume", opts)) => schedule_pause(app.clone(), interaction, opts, false)
+			.await
+			.wrap_err("command: schedule resume")?,
+		Some(("delete", opts)) => schedule_delete(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: schedule delete")?,
+		Some((other, _)) => warn!("unhandled sprint schedule subcommand: {other}"),
+		_ => warn!("bare sprint schedule command"),
+	}
+
+	Ok(())
+}
+
+pub async fn load_from_db(app: App) -> Result<()> {
+	let schedules = SprintSchedule::get_all_active(app.clone()).await?;
+	let now = Utc::now();
+
+	let mut scheduled = 0;
+	for schedule in schedules {
+		// runs missed while we were down are skipped, not caught up on
+		let after = schedule.last_run_at.map_or(now, |last| last.max(now));
+		if schedule_next(app.clone(), &schedule, after)
+			.await
+			.log()
+			.is_ok()
+		{
+			scheduled += 1;
+		}
+	}
+
+	info!(%scheduled, "loaded sprint schedules from db");
+
+	Ok(())
+}
+
+/// Record and set up a timer for the next sprint of the series after the given time.
+///
+/// Occurrences are computed in the timezone of the member who created the series.
+pub async fn schedule_next(
+	app: App,
+	schedule: &SprintSchedule,
+	after: DateTime<Utc>,
+) -> Result<DateTime<Utc>> {
+	let tz = UserPreference::get_or_create(app.clone(), schedule.member)
+		.await?
+		.timezone_tz()?;
+	let next = schedule
+		.recurrence()?
+		.next_after(&after.with_timezone(&tz))
+		.ok_or_else(|| miette!("sprint series has no next occurrence"))?
+		.with_timezone(&Utc);
+
+	schedule.set_next_run(app.clone(), next).await?;
+
+	let fire_in = (next - ANNOUNCE_AHEAD - Utc::now()).positive_or(Duration::zero());
+	debug!(?schedule.id, %next, ?fire_in, "set up sprint schedule timer");
+	app.send_timer(
+		Timer::new_after(
+			fire_in.to_std().into_diagnostic()?,
+			SprintScheduleFire::new(schedule, next),
+		)?
+		.with_key(timer_key(schedule)),
+	)
+	.await?;
+
+	Ok(next)
+}
+
+async fn schedule_new(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let channel = Channel::try_from(interaction)?;
+	let member = Member::try_from(interaction)?;
+	let settings = GuildSettings::get(app.clone(), member.guild_id).await?;
+
+	let duration = get_integer(options, "duration").unwrap_or(settings.sprint_duration.into());
+	if duration <= 0 {
+		return Err(miette!("duration must be positive"));
+	}
+	let duration = Duration::minutes(duration);
+
+	let days = get_string(options, "days").ok_or_else(|| miette!("days is a required field"))?;
+	let times = get_string(options, "times").ok_or_else(|| miette!("times is a required field"))?;
+	Recurrence::parse(days, times)?;
+
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	debug!(%days, %times, %duration, ?channel, ?member, "recording sprint schedule");
+	let schedule =
+		SprintSchedule::create(app.clone(), member, channel, (days, times), duration).await?;
+	let next = schedule_next(app.clone(), &schedule, Utc::now()).await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(format!(
+				"🔁 New sprint series `{shortid}`: {description}. The first sprint is at {next}.",
+				shortid = schedule.shortid,
+				description = describe(&schedule),
+				next = next.discord_format('f'),
+			)),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+async fn schedule_list(app: App, interaction: &Interaction) -> Result<()> {
+	let member = Member::try_from(interaction)?;
+	let schedules = SprintSchedule::get_all_in_guild(app.clone(), member.guild_id).await?;
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	let content = if schedules.is_empty() {
+		"There are no sprint series in this server.".to_string()
+	} else {
+		schedules
+			.iter()
+			.map(|schedule| {
+				let status = match (schedule.is_paused(), schedule.next_run_at) {
+					(true, _) => "paused".to_string(),
+					(false, Some(next)) => format!("next at {}", next.discord_format('f')),
+					(false, None) => "no upcoming sprint".to_string(),
+				};
+				format!(
+					"🔁 `{shortid}` in {channel}: {description} ({status})",
+					shortid = schedule.shortid,
+					channel = schedule.channel.mention(),
+					description = describe(schedule),
+				)
+			})
+			.collect::<Vec<_>>()
+			.join("\n")
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+async fn schedule_pause(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+	pause: bool,
+) -> Result<()> {
+	let schedule = get_managed_schedule(app.clone(), interaction, options).await?;
+	if schedule.is_paused() == pause {
+		return Err(miette!(
+			"sprint series `{}` is already {}",
+			schedule.shortid,
+			if pause { "paused" } else { "running" }
+		));
+	}
+
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	schedule.set_paused(app.clone(), pause).await?;
+	if pause {
+		app.cancel_timer(&timer_key(&schedule)).await?;
+	}
+
+	let content = if pause {
+		format!("⏸️ Sprint series `{}` is paused.", schedule.shortid)
+	} else {
+		let next = schedule_next(app.clone(), &schedule, Utc::now()).await?;
+		format!(
+			"▶️ Sprint series `{}` is running again, the next sprint is at {}.",
+			schedule.shortid,
+			next.discord_format('f')
+		)
+	};
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+async fn schedule_delete(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let schedule = get_managed_schedule(app.clone(), interaction, options).await?;
+	app.do_action(CommandAck::new(interaction)).await.log().ok();
+
+	schedule.delete(app.clone()).await?;
+	app.cancel_timer(&timer_key(&schedule)).await?;
+
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(format!(
+				"🗑️ Sprint series `{}` is deleted. Sprints it already announced will still run.",
+				schedule.shortid
+			)),
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+/// Get the series from the options, if the member is allowed to change it.
+///
+/// That's the member who created it, or anyone who can manage channels.
+async fn get_managed_schedule(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<SprintSchedule> {
+	let shortid =
+		get_integer(options, "series").ok_or_else(|| miette!("series is a required field"))?;
+	let member = Member::try_from(interaction)?;
+	let schedule = SprintSchedule::get_from_shortid(
+		app,
+		member.guild_id,
+		shortid
+			.try_into()
+			.into_diagnostic()
+			.wrap_err("series ID is too large")?,
+	)
+	.await
+	.wrap_err("sprint series not found")?;
+
+	let can_manage_channels = interaction
+		.member
+		.as_ref()
+		.and_then(|m| m.permissions)
+		.is_some_and(|perms| perms.contains(Permissions::MANAGE_CHANNELS));
+	if schedule.member.user_id != member.user_id && !can_manage_channels {
+		return Err(miette!(
+			"only the member who created a sprint series (or a moderator) can change it"
+		));
+	}
+
+	Ok(schedule)
+}
+
+fn timer_key(schedule: &SprintSchedule) -> String {
+	format!("sprint-schedule:{}", schedule.id)
+}
+
+fn describe(schedule: &SprintSchedule) -> String {
+	format!(
+		"{days} at {times}, for {duration}",
+		days = schedule.days,
+		times = schedule.times,
+		duration = schedule.formatted_duration(),
+	)
+}
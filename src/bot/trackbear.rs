@@ -19,32 +19,37 @@ use crate::{
 	bot::{
 		action::{CommandAck, ComponentAck, TrackbearLoginConfirm, TrackbearLoginModal},
 		context::{GenericResponse, GenericResponseData},
+		utils::trackbear_client,
 		App,
 	},
 	db::{member::Member, trackbear_login::TrackbearLogin},
 	error_ext::ErrorExt,
 };
 
+pub mod goals;
+
 #[tracing::instrument]
 pub fn command() -> Result<Command> {
-	CommandBuilder::new(
-		"trackbear",
-		"TrackBear login control",
-		CommandType::ChatInput,
+	goals::with_subcommands(
+		CommandBuilder::new(
+			"trackbear",
+			"TrackBear login, projects, and goals",
+			CommandType::ChatInput,
+		)
+		.option(SubCommandBuilder::new("status", "Check your login status"))
+		.option(SubCommandBuilder::new(
+			"login",
+			"Login to TrackBear with the bot",
+		))
+		.option(SubCommandBuilder::new(
+			"logout",
+			"Delete your TrackBear login from the bot",
+		))
+		.option(SubCommandBuilder::new(
+			"projects",
+			"List your TrackBear projects",
+		)),
 	)
-	.option(SubCommandBuilder::new("status", "Check your login status"))
-	.option(SubCommandBuilder::new(
-		"login",
-		"Login to TrackBear with the bot",
-	))
-	.option(SubCommandBuilder::new(
-		"logout",
-		"Delete your TrackBear login from the bot",
-	))
-	.option(SubCommandBuilder::new(
-		"projects",
-		"List your TrackBear projects",
-	))
 	.validate()
 	.into_diagnostic()
 	.map(|cmd| cmd.build())
@@ -76,6 +81,18 @@ pub async fn on_command(
 		Some(("projects", opts)) => list_projects(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: projects")?,
+		Some(("goals", opts)) => goals::list(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: goals")?,
+		Some(("goal-new", opts)) => goals::create(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: goal-new")?,
+		Some(("goal-edit", opts)) => goals::edit(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: goal-edit")?,
+		Some(("goal-delete", opts)) => goals::delete(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: goal-delete")?,
 		Some((other, _)) => warn!("unhandled trackbear subcommand: {other}"),
 		_ => error!("unreachable bare trackbear command"),
 	}
@@ -98,6 +115,9 @@ pub async fn on_component(
 		["set-project", project_id] => set_project(app.clone(), interaction, project_id)
 			.await
 			.wrap_err("action: set-project")?,
+		["goal-delete", goal_id] => goals::confirm_delete(app.clone(), interaction, goal_id)
+			.await
+			.wrap_err("action: goal-delete")?,
 		id => warn!(?id, "unhandled trackbear component action"),
 	}
 
@@ -134,7 +154,7 @@ async fn status(app: App, interaction: &Interaction, _options: &[CommandDataOpti
 			content: Some(
 				if let Some(login) = TrackbearLogin::get_for_member(app.clone(), member).await? {
 					debug!(?login.id, ?member, "checking trackbear credentials");
-					if login.client().await.is_ok() {
+					if login.client(app.clone()).await.is_ok() {
 						"🙌 You're logged in to TrackBear!".to_string()
 					} else {
 						"⁉️ I've got credentials for you but they're not working".to_string()
@@ -210,7 +230,7 @@ async fn login(
 
 	debug!(?login.id, "checking trackbear credentials");
 	let _ = login
-		.client()
+		.client(app.clone())
 		.await
 		.wrap_err("couldn't login to trackbear!")?;
 	debug!(?login.id, "successfully logged into trackbear");
@@ -264,9 +284,7 @@ async fn list_projects(
 		.log()
 		.ok();
 
-	let client = TrackbearLogin::client_for_member(app.clone(), member)
-		.await?
-		.ok_or_else(|| miette!("You need to /trackbear login first!"))?;
+	let client = trackbear_client(app.clone(), member).await?;
 	let mut projects = client.list_projects().await?;
 
 	// Get the current project for this member
@@ -353,9 +371,7 @@ async fn set_project(app: App, interaction: &Interaction, project_id_str: &str)
 		.log()
 		.ok();
 
-	let client = TrackbearLogin::client_for_member(app.clone(), member)
-		.await?
-		.ok_or_else(|| miette!("You need to /trackbear login first!"))?;
+	let client = trackbear_client(app.clone(), member).await?;
 
 	// Verify the project exists and user has access
 	let projects = client.list_projects().await?;
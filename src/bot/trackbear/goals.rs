@@ -0,0 +1,418 @@
+use std::{fmt::Write, str::FromStr};
+
+use chrono::{NaiveDate, Utc};
+use miette::{miette, Context, IntoDiagnostic, Result};
+use twilight_model::{
+	application::interaction::{application_command::CommandDataOption, Interaction},
+	channel::message::component::{Button, ButtonStyle, Component},
+};
+use twilight_util::builder::command::{
+	CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	bot::{
+		action::{CommandAck, ComponentAck},
+		utils::{
+			action_row,
+			command::{get_integer, get_string},
+			respond_ephemeral, trackbear_client,
+		},
+		App,
+	},
+	db::{member::Member, project::Project},
+	error_ext::ErrorExt,
+	trackbear::{
+		client::{
+			CadenceUnit, CreateGoalRequest, Goal, GoalCadence, GoalParameters, GoalThreshold,
+			GoalType, Measure, UpdateGoalRequest,
+		},
+		project::ActiveGoal,
+		TrackbearClient,
+	},
+};
+
+/// Add the goal subcommands to the trackbear command.
+pub fn with_subcommands(command: CommandBuilder) -> CommandBuilder {
+	command
+		.option(SubCommandBuilder::new(
+			"goals",
+			"List your active TrackBear goals and how they're going",
+		))
+		.option(
+			SubCommandBuilder::new("goal-new", "Create a TrackBear goal")
+				.option(StringBuilder::new("title", "Name of the goal, like NaNoWriMo").required(true))
+				.option(
+					IntegerBuilder::new("count", "How much to write, like 50000")
+						.min_value(1)
+						.required(true),
+				)
+				.option(
+					StringBuilder::new(
+						"kind",
+						"Reach a total by the end date, or write every day/week (defaults to a total)",
+					)
+					.choices(vec![("Target", "target"), ("Habit", "habit")]),
+				)
+				.option(measure_option())
+				.option(StringBuilder::new(
+					"start",
+					"First day of the goal, like 2024-11-01 (defaults to today)",
+				))
+				.option(StringBuilder::new(
+					"end",
+					"Last day of the goal, like 2024-11-30",
+				))
+				.option(
+					StringBuilder::new("every", "How often a habit repeats (defaults to every day)")
+						.choices(vec![
+							("Day", "day"),
+							("Week", "week"),
+							("Month", "month"),
+							("Year", "year"),
+						]),
+				)
+				.option(IntegerBuilder::new(
+					"project",
+					"TrackBear project ID (defaults to your current project)",
+				)),
+		)
+		.option(
+			SubCommandBuilder::new("goal-edit", "Change a TrackBear goal")
+				.option(goal_option())
+				.option(StringBuilder::new("title", "New name of the goal"))
+				.option(IntegerBuilder::new("count", "New amount to write").min_value(1))
+				.option(StringBuilder::new(
+					"start",
+					"New first day of the goal, like 2024-11-01",
+				))
+				.option(StringBuilder::new(
+					"end",
+					"New last day of the goal, like 2024-11-30",
+				)),
+		)
+		.option(
+			SubCommandBuilder::new("goal-delete", "Delete a TrackBear goal").option(goal_option()),
+		)
+}
+
+fn goal_option() -> IntegerBuilder {
+	IntegerBuilder::new("goal", "TrackBear goal ID, from /trackbear goals").required(true)
+}
+
+fn measure_option() -> StringBuilder {
+	StringBuilder::new("measure", "What to count (defaults to words)").choices(vec![
+		("Words", "word"),
+		("Minutes", "time"),
+		("Pages", "page"),
+		("Chapters", "chapter"),
+		("Scenes", "scene"),
+		("Lines", "line"),
+	])
+}
+
+pub async fn list(
+	app: App,
+	interaction: &Interaction,
+	_options: &[CommandDataOption],
+) -> Result<()> {
+	let (_, client) = ack_and_login(app.clone(), interaction).await?;
+	let goals = ActiveGoal::fetch_all(&client).await?;
+
+	let content = if goals.is_empty() {
+		"You don't have any active goals in TrackBear! Make one with `/trackbear goal-new`."
+			.to_string()
+	} else {
+		let mut content = String::with_capacity(goals.len() * 100);
+		for active in &goals {
+			writeln!(content, "- {}", describe(&active.goal)).ok();
+
+			if let Some(prog) = &active.progress {
+				write!(
+					content,
+					"  {current}/{target} words, {percent:.1}% done",
+					current = prog.current,
+					target = prog.target,
+					percent = prog.percent,
+				)
+				.ok();
+				if !prog.achieved {
+					write!(
+						content,
+						", {} with {} days left",
+						prog.format_tracking(),
+						prog.days_remaining
+					)
+					.ok();
+				}
+				if let Some(project) = &active.project {
+					write!(content, " on _{}_", project.title).ok();
+				}
+				content.push('\n');
+			}
+		}
+		content
+	};
+
+	respond_ephemeral(app, interaction, content, Vec::new()).await
+}
+
+pub async fn create(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let title = get_string(options, "title")
+		.map(|title| title.trim().to_string())
+		.filter(|title| !title.is_empty())
+		.ok_or_else(|| miette!("the goal needs a title"))?;
+	let count = get_integer(options, "count")
+		.filter(|count| *count > 0)
+		.ok_or_else(|| miette!("the goal needs a count above zero"))?;
+	let goal_type = match get_string(options, "kind") {
+		Some("habit") => GoalType::Habit,
+		_ => GoalType::Target,
+	};
+	let measure = get_string(options, "measure")
+		.map(Measure::from_str)
+		.transpose()
+		.map_err(|err| miette!("{err}"))?
+		.unwrap_or(Measure::Word);
+	let cadence = get_string(options, "every")
+		.map(CadenceUnit::from_str)
+		.transpose()
+		.map_err(|err| miette!("{err}"))?;
+	if cadence.is_some() && goal_type != GoalType::Habit {
+		return Err(miette!("only habit goals repeat, pick the habit kind"));
+	}
+
+	let start = get_string(options, "start")
+		.map(parse_date)
+		.transpose()?
+		.unwrap_or_else(|| Utc::now().date_naive());
+	let end = get_string(options, "end").map(parse_date).transpose()?;
+	if end.is_some_and(|end| end < start) {
+		return Err(miette!("the goal can't end before it starts"));
+	}
+
+	let (member, client) = ack_and_login(app.clone(), interaction).await?;
+	let project = match get_integer(options, "project") {
+		Some(id) => Some(id),
+		None => Project::get_for_member(app.clone(), member)
+			.await?
+			.map(|p| p.trackbear_id),
+	};
+
+	let goal = client
+		.create_goal(CreateGoalRequest {
+			title,
+			description: String::new(),
+			goal_type,
+			parameters: GoalParameters {
+				threshold: Some(GoalThreshold { measure, count }),
+				cadence: (goal_type == GoalType::Habit).then(|| GoalCadence {
+					unit: cadence.unwrap_or(CadenceUnit::Day),
+					period: 1,
+				}),
+			},
+			start_date: Some(format_date(start)),
+			end_date: end.map(format_date),
+			work_ids: project.into_iter().collect(),
+			tag_ids: Vec::new(),
+			starred: false,
+			display_on_profile: false,
+		})
+		.await
+		.wrap_err("couldn't create the goal")?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("🎯 Created {}", describe(&goal)),
+		Vec::new(),
+	)
+	.await
+}
+
+pub async fn edit(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let goal_id = get_integer(options, "goal").ok_or_else(|| miette!("goal is required"))?;
+	let title = get_string(options, "title")
+		.map(|title| title.trim().to_string())
+		.filter(|title| !title.is_empty());
+	let count = get_integer(options, "count");
+	if count.is_some_and(|count| count <= 0) {
+		return Err(miette!("the goal needs a count above zero"));
+	}
+	let start = get_string(options, "start").map(parse_date).transpose()?;
+	let end = get_string(options, "end").map(parse_date).transpose()?;
+	if title.is_none() && count.is_none() && start.is_none() && end.is_none() {
+		return Err(miette!("nothing to change"));
+	}
+
+	let (_, client) = ack_and_login(app.clone(), interaction).await?;
+	let goal = find_goal(&client, goal_id).await?;
+
+	// a date that isn't changing is still checked against the one that is
+	let start_after = start.or_else(|| goal.start_date.as_deref().and_then(|d| parse_date(d).ok()));
+	let end_after = end.or_else(|| goal.end_date.as_deref().and_then(|d| parse_date(d).ok()));
+	if let (Some(start), Some(end)) = (start_after, end_after) {
+		if end < start {
+			return Err(miette!("the goal can't end before it starts"));
+		}
+	}
+
+	let parameters = count.map(|count| {
+		let mut parameters = goal.parameters.clone();
+		let measure = parameters
+			.threshold
+			.as_ref()
+			.map_or(Measure::Word, |threshold| threshold.measure.clone());
+		parameters.threshold = Some(GoalThreshold { measure, count });
+		parameters
+	});
+
+	let goal = client
+		.update_goal(
+			goal.id,
+			UpdateGoalRequest {
+				title,
+				parameters,
+				start_date: start.map(format_date),
+				end_date: end.map(format_date),
+			},
+		)
+		.await
+		.wrap_err("couldn't change the goal")?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("✏️ Updated {}", describe(&goal)),
+		Vec::new(),
+	)
+	.await
+}
+
+/// Ask to delete a goal.
+///
+/// TrackBear has no way to archive a goal (its state is only ever active or deleted), so this
+/// deletes it instead, after the member confirms.
+pub async fn delete(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let goal_id = get_integer(options, "goal").ok_or_else(|| miette!("goal is required"))?;
+
+	let (_, client) = ack_and_login(app.clone(), interaction).await?;
+	let goal = find_goal(&client, goal_id).await?;
+
+	// it can't be brought back from here, so make sure
+	respond_ephemeral(
+		app,
+		interaction,
+		format!(
+			"Delete {}? It'll be gone from TrackBear, and I can't bring it back.",
+			describe(&goal)
+		),
+		action_row(vec![Component::Button(Button {
+			custom_id: Some(format!("trackbear:goal-delete:{}", goal.id)),
+			disabled: false,
+			emoji: None,
+			label: Some("Delete goal".to_string()),
+			style: ButtonStyle::Danger,
+			url: None,
+			sku_id: None,
+		})]),
+	)
+	.await
+}
+
+/// Delete the goal once the member has confirmed it.
+pub async fn confirm_delete(app: App, interaction: &Interaction, goal_id: &str) -> Result<()> {
+	let goal_id = i64::from_str(goal_id).into_diagnostic()?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(ComponentAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let client = trackbear_client(app.clone(), member).await?;
+	let goal = find_goal(&client, goal_id).await?;
+	client
+		.delete_goal(goal.id)
+		.await
+		.wrap_err("couldn't delete the goal")?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("🗑️ Deleted **{}**.", goal.title),
+		Vec::new(),
+	)
+	.await
+}
+
+/// One line about the goal: its name, ID, and what it's asking for.
+fn describe(goal: &Goal) -> String {
+	let mut line = format!("**{}** (`{}`)", goal.title, goal.id);
+
+	if let Some(threshold) = &goal.parameters.threshold {
+		write!(line, ": {} {}", threshold.count, threshold.measure.plural()).ok();
+	}
+
+	match (goal.goal_type, &goal.parameters.cadence) {
+		(GoalType::Habit, Some(cadence)) if cadence.period > 1 => {
+			write!(line, " every {} {}s", cadence.period, cadence.unit.name()).ok();
+		}
+		(GoalType::Habit, Some(cadence)) => {
+			write!(line, " every {}", cadence.unit.name()).ok();
+		}
+		_ => {}
+	}
+
+	if let Some(end) = &goal.end_date {
+		write!(line, ", until {end}").ok();
+	}
+
+	line
+}
+
+async fn find_goal(client: &TrackbearClient, goal_id: i64) -> Result<Goal> {
+	client
+		.list_goals()
+		.await?
+		.into_iter()
+		.find(|g| g.id == goal_id)
+		.ok_or_else(|| {
+			miette!(
+				"Goal with ID {} not found in your TrackBear account",
+				goal_id
+			)
+		})
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate> {
+	NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+		.into_diagnostic()
+		.wrap_err_with(|| format!("{date} isn't a date like 2024-11-30"))
+}
+
+fn format_date(date: NaiveDate) -> String {
+	date.format("%Y-%m-%d").to_string()
+}
+
+async fn ack_and_login(app: App, interaction: &Interaction) -> Result<(Member, TrackbearClient)> {
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let client = trackbear_client(app, member).await?;
+	Ok((member, client))
+}
@@ -1,9 +1,78 @@
-use twilight_model::channel::message::component::{ActionRow, Component};
+use miette::{miette, Result};
+use twilight_model::{
+	application::interaction::Interaction,
+	channel::message::component::{ActionRow, Component},
+};
 
+use crate::{
+	bot::{
+		context::{GenericResponse, GenericResponseData},
+		App,
+	},
+	db::{member::Member, trackbear_login::TrackbearLogin},
+	trackbear::TrackbearClient,
+};
+
+pub mod card;
 pub mod command;
+pub mod permissions;
 pub mod pretties;
+pub mod recurrence;
 pub mod time;
 
 pub fn action_row(components: Vec<Component>) -> Vec<Component> {
 	vec![Component::ActionRow(ActionRow { components })]
 }
+
+/// Reply to the interaction with a message only the member can see.
+pub async fn respond_ephemeral(
+	app: App,
+	interaction: &Interaction,
+	content: String,
+	components: Vec<Component>,
+) -> Result<()> {
+	app.send_response(GenericResponse::from_interaction(
+		interaction,
+		GenericResponseData {
+			content: Some(content),
+			components,
+			ephemeral: true,
+			..Default::default()
+		},
+	))
+	.await
+	.map(drop)
+}
+
+/// The member's TrackBear client, or an error telling them to log in.
+pub async fn trackbear_client(app: App, member: Member) -> Result<TrackbearClient> {
+	TrackbearLogin::client_for_member(app, member)
+		.await?
+		.ok_or_else(|| miette!("You need to /trackbear login first!"))
+}
+
+/// Rank sorted values, giving equal values the same rank (1, 2, 2, 4).
+pub fn ranks(values: &[f64]) -> Vec<usize> {
+	let mut ranks: Vec<usize> = Vec::with_capacity(values.len());
+	for (i, value) in values.iter().enumerate() {
+		let rank = match (i.checked_sub(1), ranks.last()) {
+			(Some(prev), Some(&rank)) if values[prev] == *value => rank,
+			_ => i + 1,
+		};
+		ranks.push(rank);
+	}
+	ranks
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn ties_share_a_rank() {
+		assert_eq!(ranks(&[]), Vec::<usize>::new());
+		assert_eq!(ranks(&[30.0, 20.0, 10.0]), vec![1, 2, 3]);
+		assert_eq!(ranks(&[30.0, 20.0, 20.0, 10.0]), vec![1, 2, 2, 4]);
+		assert_eq!(ranks(&[5.0, 5.0, 5.0]), vec![1, 1, 1]);
+	}
+}
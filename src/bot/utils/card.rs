@@ -0,0 +1,192 @@
+use std::{fmt::Write, sync::Arc, sync::OnceLock};
+
+use miette::{miette, IntoDiagnostic, Result};
+use resvg::{tiny_skia, usvg};
+
+const WIDTH: u32 = 800;
+const HEADER_HEIGHT: u32 = 110;
+const ROW_HEIGHT: u32 = 56;
+const PADDING: u32 = 32;
+
+/// The bundled font first, then whatever the system has for emoji and other scripts.
+const FONT_FAMILY: &str = "'DejaVu Sans', 'Noto Color Emoji', sans-serif";
+
+const FONTS: [&[u8]; 2] = [
+	include_bytes!("../../../assets/fonts/DejaVuSans.ttf"),
+	include_bytes!("../../../assets/fonts/DejaVuSans-Bold.ttf"),
+];
+
+/// A summary of a sprint, to be rendered as an image.
+#[derive(Debug, Clone)]
+pub struct SummaryCard {
+	pub title: String,
+	pub subtitle: String,
+	pub rows: Vec<SummaryCardRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SummaryCardRow {
+	pub rank: usize,
+	pub name: String,
+	/// The count, decorated with its pretties.
+	pub count: String,
+	pub rate: String,
+}
+
+impl SummaryCard {
+	pub fn to_svg(&self) -> String {
+		let height = HEADER_HEIGHT + ROW_HEIGHT * (self.rows.len().max(1) as u32) + PADDING;
+
+		let mut svg = String::new();
+		// UNWRAP for all: writing to a String can't fail
+		write!(
+			svg,
+			r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="{FONT_FAMILY}">"##
+		)
+		.unwrap();
+		write!(
+			svg,
+			r##"<rect width="100%" height="100%" rx="16" fill="#1e1f29"/>"##
+		)
+		.unwrap();
+		write!(
+			svg,
+			r##"<text x="{PADDING}" y="56" font-size="34" font-weight="bold" fill="#f8f8f2">{}</text>"##,
+			escape(&self.title)
+		)
+		.unwrap();
+		write!(
+			svg,
+			r##"<text x="{PADDING}" y="88" font-size="20" fill="#a4a8c0">{}</text>"##,
+			escape(&self.subtitle)
+		)
+		.unwrap();
+
+		if self.rows.is_empty() {
+			write!(
+				svg,
+				r##"<text x="{PADDING}" y="{}" font-size="24" fill="#a4a8c0">Nobody sprinted.</text>"##,
+				HEADER_HEIGHT + ROW_HEIGHT / 2 + 8
+			)
+			.unwrap();
+		}
+
+		for (i, row) in self.rows.iter().enumerate() {
+			let top = HEADER_HEIGHT + ROW_HEIGHT * i as u32;
+			let baseline = top + ROW_HEIGHT / 2 + 9;
+			if i % 2 == 0 {
+				write!(
+					svg,
+					r##"<rect x="16" y="{top}" width="{}" height="{ROW_HEIGHT}" rx="8" fill="#282a36"/>"##,
+					WIDTH - 32
+				)
+				.unwrap();
+			}
+
+			let rank_colour = match row.rank {
+				1 => "#ffd866",
+				2 => "#d0d4e4",
+				3 => "#e0a370",
+				_ => "#a4a8c0",
+			};
+			write!(
+				svg,
+				r##"<text x="{PADDING}" y="{baseline}" font-size="26" font-weight="bold" fill="{rank_colour}">{}</text>"##,
+				row.rank
+			)
+			.unwrap();
+			write!(
+				svg,
+				r##"<text x="{}" y="{baseline}" font-size="26" fill="#f8f8f2">{}</text>"##,
+				PADDING + 56,
+				escape(&truncate(&row.name, 20))
+			)
+			.unwrap();
+			write!(
+				svg,
+				r##"<text x="{}" y="{baseline}" font-size="26" font-weight="bold" fill="#50fa7b" text-anchor="end">{}</text>"##,
+				WIDTH - PADDING - 270,
+				escape(&row.count)
+			)
+			.unwrap();
+			write!(
+				svg,
+				r##"<text x="{}" y="{baseline}" font-size="20" fill="#a4a8c0" text-anchor="end">{}</text>"##,
+				WIDTH - PADDING,
+				escape(&row.rate)
+			)
+			.unwrap();
+		}
+
+		svg.push_str("</svg>");
+		svg
+	}
+
+	/// Render the card to a PNG, using the bundled font and any installed on the system.
+	pub fn to_png(&self) -> Result<Vec<u8>> {
+		let options = usvg::Options {
+			fontdb: fonts(),
+			..Default::default()
+		};
+		let tree = usvg::Tree::from_str(&self.to_svg(), &options).into_diagnostic()?;
+
+		let size = tree.size().to_int_size();
+		let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+			.ok_or_else(|| miette!("summary card has no size"))?;
+		resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+		pixmap.encode_png().into_diagnostic()
+	}
+}
+
+fn fonts() -> Arc<usvg::fontdb::Database> {
+	static DB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+	DB.get_or_init(|| {
+		let mut db = usvg::fontdb::Database::new();
+		// bundled so there's always text, even on hosts without any fonts installed
+		for font in FONTS {
+			db.load_font_data(font.to_vec());
+		}
+		db.set_sans_serif_family("DejaVu Sans");
+		db.load_system_fonts();
+		Arc::new(db)
+	})
+	.clone()
+}
+
+fn escape(s: &str) -> String {
+	s.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+fn truncate(s: &str, max: usize) -> String {
+	if s.chars().count() > max {
+		format!("{}…", s.chars().take(max - 1).collect::<String>())
+	} else {
+		s.to_string()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn svg_is_escaped_and_parses() {
+		let card = SummaryCard {
+			title: "Sprint 1234".into(),
+			subtitle: "20m".into(),
+			rows: vec![SummaryCardRow {
+				rank: 1,
+				name: "<Tom & Jerry>".into(),
+				count: "500".into(),
+				rate: "25.0 words per minute".into(),
+			}],
+		};
+
+		let svg = card.to_svg();
+		assert!(svg.contains("&lt;Tom &amp; Jerry&gt;"));
+		assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+	}
+}
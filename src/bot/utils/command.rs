@@ -1,5 +1,6 @@
-use twilight_model::application::interaction::application_command::{
-	CommandDataOption, CommandOptionValue,
+use twilight_model::{
+	application::interaction::application_command::{CommandDataOption, CommandOptionValue},
+	id::{marker::RoleMarker, Id},
 };
 
 pub fn get_option<'o>(
@@ -44,3 +45,13 @@ pub fn get_boolean(options: &[CommandDataOption], name: &str) -> Option<bool> {
 		}
 	})
 }
+
+pub fn get_role(options: &[CommandDataOption], name: &str) -> Option<Id<RoleMarker>> {
+	get_option(options, name).and_then(|val| {
+		if let CommandOptionValue::Role(id) = val {
+			Some(*id)
+		} else {
+			None
+		}
+	})
+}
@@ -0,0 +1,60 @@
+use miette::{miette, IntoDiagnostic, Result};
+use twilight_model::{
+	guild::Permissions,
+	id::{
+		marker::{GuildMarker, RoleMarker},
+		Id,
+	},
+};
+use twilight_util::permission_calculator::PermissionCalculator;
+
+use crate::{bot::App, db::member::Member};
+
+/// The member's permissions across the whole guild, from its roles and ownership.
+///
+/// This asks Discord rather than trusting the permissions sent along with an interaction, so
+/// it also works for actions which didn't come from one (buttons on old messages, timers).
+#[tracing::instrument(skip(app))]
+pub async fn guild_permissions(app: App, member: Member) -> Result<Permissions> {
+	let guild_id: Id<GuildMarker> = member.into();
+	let guild = app
+		.client
+		.guild(guild_id)
+		.await
+		.into_diagnostic()?
+		.model()
+		.await
+		.into_diagnostic()?;
+	let discord_member = member.to_member(app).await?;
+
+	// the @everyone role has the same ID as the guild
+	let everyone = guild
+		.roles
+		.iter()
+		.find(|role| role.id == guild_id.cast())
+		.map_or(Permissions::empty(), |role| role.permissions);
+	let roles: Vec<(Id<RoleMarker>, Permissions)> = guild
+		.roles
+		.iter()
+		.filter(|role| discord_member.roles.contains(&role.id))
+		.map(|role| (role.id, role.permissions))
+		.collect();
+
+	Ok(
+		PermissionCalculator::new(guild_id, member.into(), everyone, &roles)
+			.owner_id(guild.owner_id)
+			.root(),
+	)
+}
+
+/// Error unless the member can manage the guild (or is an administrator).
+pub async fn require_manage_guild(app: App, member: Member) -> Result<()> {
+	if guild_permissions(app, member)
+		.await?
+		.contains(Permissions::MANAGE_GUILD)
+	{
+		Ok(())
+	} else {
+		Err(miette!("only server admins can do that"))
+	}
+}
@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Weekday};
+use miette::{miette, IntoDiagnostic, Result};
+
+const ALL_DAYS: [Weekday; 7] = [
+	Weekday::Mon,
+	Weekday::Tue,
+	Weekday::Wed,
+	Weekday::Thu,
+	Weekday::Fri,
+	Weekday::Sat,
+	Weekday::Sun,
+];
+
+/// A weekly recurrence: a set of days, and a set of local times on each of those days.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+	pub days: Vec<Weekday>,
+	pub times: Vec<NaiveTime>,
+}
+
+impl Recurrence {
+	/// Parse a recurrence from its days and times parts.
+	///
+	/// Days are either `daily`, `weekdays`, `weekends`, or a list of day names (`mon, wed, fri`).
+	///
+	/// Times are a comma-separated list of clock times (`10:00, 14:30`) and/or ranges with a step
+	/// in minutes (`18:00-22:00/30` for every half hour from 6pm to 10pm inclusive).
+	pub fn parse(days: &str, times: &str) -> Result<Self> {
+		Ok(Self {
+			days: parse_days(days)?,
+			times: parse_times(times)?,
+		})
+	}
+
+	/// The first occurrence strictly after the given time, in the same timezone.
+	///
+	/// Local times which don't exist on a particular day (DST gaps) are skipped.
+	pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+		let tz = after.timezone();
+		let start = after.date_naive();
+		(0..=7)
+			.filter_map(|n| start.checked_add_signed(Duration::days(n)))
+			.filter(|date| self.days.contains(&date.weekday()))
+			.flat_map(|date| self.times.iter().map(move |time| date.and_time(*time)))
+			.filter_map(|local| tz.from_local_datetime(&local).earliest())
+			.find(|candidate| candidate > after)
+	}
+}
+
+fn parse_days(s: &str) -> Result<Vec<Weekday>> {
+	let s = s.trim().to_ascii_lowercase();
+	let mut days = match s.as_str() {
+		"daily" | "every day" | "everyday" | "all" => ALL_DAYS.to_vec(),
+		"weekdays" => ALL_DAYS[..5].to_vec(),
+		"weekends" | "weekend" => ALL_DAYS[5..].to_vec(),
+		list => list
+			.split([',', ' '])
+			.map(str::trim)
+			.filter(|day| !day.is_empty())
+			.map(parse_weekday)
+			.collect::<Result<Vec<_>>>()?,
+	};
+
+	days.sort_by_key(|day| day.num_days_from_monday());
+	days.dedup();
+
+	if days.is_empty() {
+		Err(miette!("at least one day is needed"))
+	} else {
+		Ok(days)
+	}
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+	// full names or three-letter abbreviations, like "monday" or "mon"
+	Weekday::from_str(s).map_err(|_| miette!("unknown day: {s}"))
+}
+
+fn parse_times(s: &str) -> Result<Vec<NaiveTime>> {
+	let mut times = Vec::new();
+	for part in s.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+		let Some((range, step)) = part.split_once('/') else {
+			times.push(parse_time(part)?);
+			continue;
+		};
+
+		let (from, to) = range
+			.split_once('-')
+			.ok_or_else(|| miette!("a step needs a range, like 18:00-22:00/30"))?;
+		let (from, to) = (parse_time(from)?, parse_time(to)?);
+		if to < from {
+			return Err(miette!("ranges can't go past midnight: {part}"));
+		}
+
+		let step = u16::from_str(step.trim().trim_end_matches(['m', 'M'])).into_diagnostic()?;
+		if step < 5 {
+			return Err(miette!(
+				"sprints can't be scheduled less than 5 minutes apart"
+			));
+		}
+
+		let mut time = from;
+		while time <= to {
+			times.push(time);
+			let (next, wrapped) = time.overflowing_add_signed(Duration::minutes(step as _));
+			if wrapped != 0 {
+				break;
+			}
+			time = next;
+		}
+	}
+
+	times.sort();
+	times.dedup();
+
+	if times.is_empty() {
+		Err(miette!("at least one time is needed"))
+	} else {
+		Ok(times)
+	}
+}
+
+fn parse_time(s: &str) -> Result<NaiveTime> {
+	let s = s.trim();
+	if let Ok(time) = NaiveTime::parse_from_str(s, "%_H:%M") {
+		return Ok(time);
+	}
+
+	NaiveTime::parse_from_str(s, "%H:%M").into_diagnostic()
+}
+
+#[cfg(test)]
+mod test {
+	use chrono::{NaiveTime, TimeZone, Weekday};
+	use chrono_tz::Pacific;
+
+	use super::Recurrence;
+
+	fn hm(h: u32, m: u32) -> NaiveTime {
+		NaiveTime::from_hms_opt(h, m, 0).unwrap()
+	}
+
+	#[test]
+	fn parses_day_keywords() {
+		assert_eq!(Recurrence::parse("daily", "10:00").unwrap().days.len(), 7);
+		assert_eq!(
+			Recurrence::parse("Weekdays", "10:00").unwrap().days,
+			vec![
+				Weekday::Mon,
+				Weekday::Tue,
+				Weekday::Wed,
+				Weekday::Thu,
+				Weekday::Fri
+			]
+		);
+		assert_eq!(
+			Recurrence::parse("weekends", "10:00").unwrap().days,
+			vec![Weekday::Sat, Weekday::Sun]
+		);
+	}
+
+	#[test]
+	fn parses_day_lists() {
+		assert_eq!(
+			Recurrence::parse("fri, mon wed", "10:00").unwrap().days,
+			vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]
+		);
+		assert_eq!(
+			Recurrence::parse("Saturday", "10:00").unwrap().days,
+			vec![Weekday::Sat]
+		);
+		assert!(Recurrence::parse("caturday", "10:00").is_err());
+		assert!(Recurrence::parse("monkey", "10:00").is_err());
+		assert!(Recurrence::parse("sunflower", "10:00").is_err());
+	}
+
+	#[test]
+	fn parses_time_lists_and_ranges() {
+		assert_eq!(
+			Recurrence::parse("daily", "19:30, 9:00").unwrap().times,
+			vec![hm(9, 0), hm(19, 30)]
+		);
+		assert_eq!(
+			Recurrence::parse("daily", "18:00-19:00/30").unwrap().times,
+			vec![hm(18, 0), hm(18, 30), hm(19, 0)]
+		);
+		assert_eq!(
+			Recurrence::parse("daily", "18:00-19:00/30m, 18:30")
+				.unwrap()
+				.times,
+			vec![hm(18, 0), hm(18, 30), hm(19, 0)]
+		);
+	}
+
+	#[test]
+	fn rejects_bad_times() {
+		assert!(Recurrence::parse("daily", "").is_err());
+		assert!(Recurrence::parse("daily", "25:00").is_err());
+		assert!(Recurrence::parse("daily", "18:00-19:00/1").is_err());
+		assert!(Recurrence::parse("daily", "22:00-18:00/30").is_err());
+	}
+
+	#[test]
+	fn finds_next_weekly_occurrence() {
+		let rule = Recurrence::parse("sat", "10:00").unwrap();
+		// a wednesday
+		let after = Pacific::Auckland
+			.with_ymd_and_hms(2024, 5, 1, 12, 0, 0)
+			.unwrap();
+		assert_eq!(
+			rule.next_after(&after).unwrap(),
+			Pacific::Auckland
+				.with_ymd_and_hms(2024, 5, 4, 10, 0, 0)
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn finds_next_occurrence_later_today() {
+		let rule = Recurrence::parse("daily", "18:00-20:00/30").unwrap();
+		let after = Pacific::Auckland
+			.with_ymd_and_hms(2024, 5, 1, 18, 10, 0)
+			.unwrap();
+		assert_eq!(
+			rule.next_after(&after).unwrap(),
+			Pacific::Auckland
+				.with_ymd_and_hms(2024, 5, 1, 18, 30, 0)
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn next_occurrence_is_strictly_after() {
+		let rule = Recurrence::parse("daily", "18:00").unwrap();
+		let after = Pacific::Auckland
+			.with_ymd_and_hms(2024, 5, 1, 18, 0, 0)
+			.unwrap();
+		assert_eq!(
+			rule.next_after(&after).unwrap(),
+			Pacific::Auckland
+				.with_ymd_and_hms(2024, 5, 2, 18, 0, 0)
+				.unwrap()
+		);
+	}
+}
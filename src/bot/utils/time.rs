@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
-use chrono::{naive::NaiveTime, DateTime, Duration, Timelike, Utc};
+use chrono::{
+	naive::{NaiveDate, NaiveTime},
+	DateTime, Datelike, Duration, Timelike, Utc, Weekday,
+};
 use miette::{miette, IntoDiagnostic, Result};
+use pg_interval::Interval;
 
 pub trait ChronoDurationExt {
 	fn positive_or(self, default: Duration) -> Duration;
@@ -27,6 +31,18 @@ impl ChronoDurationExt for Duration {
 	}
 }
 
+pub trait IntervalExt {
+	/// Convert to a chrono duration, counting months as 31 days.
+	fn to_duration(&self) -> Duration;
+}
+
+impl IntervalExt for Interval {
+	fn to_duration(&self) -> Duration {
+		Duration::days(self.days as i64 + self.months as i64 * 31)
+			+ Duration::microseconds(self.microseconds)
+	}
+}
+
 pub trait ChronoDateTimeExt {
 	/// Duration since the given time, or None if it's in the future.
 	fn elapsed(&self) -> Result<Option<std::time::Duration>>;
@@ -124,13 +140,46 @@ pub fn parse_when_relative_to(now: NaiveTime, s: &str) -> Result<NaiveTime> {
 	NaiveTime::parse_from_str(s, "%H:%M").into_diagnostic()
 }
 
+/// Parse a day in the past or today, like `yesterday`, `3 days ago`, `monday` or `2024-11-03`.
+///
+/// Weekdays are the most recent one, which is today if it's that day.
+pub fn parse_day_relative_to(today: NaiveDate, s: &str) -> Result<NaiveDate> {
+	let s = s.trim().to_ascii_lowercase();
+
+	match s.as_str() {
+		"today" => return Ok(today),
+		"yesterday" => return Ok(today - Duration::days(1)),
+		_ => {}
+	}
+
+	if let Some(days) = s
+		.strip_suffix("days ago")
+		.or_else(|| s.strip_suffix("day ago"))
+		.and_then(|s| u16::from_str(s.trim()).ok())
+	{
+		return Ok(today - Duration::days(days as _));
+	}
+
+	if let Ok(weekday) = Weekday::from_str(&s) {
+		let days_back =
+			(7 + today.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+		return Ok(today - Duration::days(days_back as _));
+	}
+
+	NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+		.map_err(|_| miette!("{s} isn't a day like yesterday, monday, or 2024-11-30"))
+}
+
 #[cfg(test)]
 mod test {
-	use chrono::{naive::NaiveTime, DateTime, Duration, Utc};
+	use chrono::{
+		naive::{NaiveDate, NaiveTime},
+		DateTime, Duration, Utc,
+	};
 	use chrono_tz::{Pacific, Tz};
 	use miette::Result;
 
-	use super::parse_when_relative_to;
+	use super::{parse_day_relative_to, parse_when_relative_to};
 
 	fn now_in_tz() -> DateTime<Tz> {
 		let now = Utc::now();
@@ -237,4 +286,33 @@ mod test {
 			NaiveTime::from_hms_opt(0, 12, 0).unwrap()
 		);
 	}
+
+	#[test]
+	fn parses_relative_days() {
+		// a Sunday
+		let today = NaiveDate::from_ymd_opt(2024, 11, 3).unwrap();
+		let day = |s| parse_day_relative_to(today, s).unwrap();
+
+		assert_eq!(day("today"), today);
+		assert_eq!(
+			day("Yesterday"),
+			NaiveDate::from_ymd_opt(2024, 11, 2).unwrap()
+		);
+		assert_eq!(
+			day("1 day ago"),
+			NaiveDate::from_ymd_opt(2024, 11, 2).unwrap()
+		);
+		assert_eq!(
+			day("5 days ago"),
+			NaiveDate::from_ymd_opt(2024, 10, 29).unwrap()
+		);
+		assert_eq!(day("friday"), NaiveDate::from_ymd_opt(2024, 11, 1).unwrap());
+		assert_eq!(day("mon"), NaiveDate::from_ymd_opt(2024, 10, 28).unwrap());
+		assert_eq!(day("sunday"), today);
+		assert_eq!(
+			day("2024-10-31"),
+			NaiveDate::from_ymd_opt(2024, 10, 31).unwrap()
+		);
+		assert!(parse_day_relative_to(today, "someday").is_err());
+	}
 }
@@ -1,11 +1,13 @@
 use std::str::FromStr;
 
+use chrono::{NaiveDate, Utc};
 use miette::{miette, Context, IntoDiagnostic, Result};
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 use twilight_model::application::{
 	command::{Command, CommandType},
 	interaction::{
 		application_command::{CommandData, CommandDataOption, CommandOptionValue},
+		message_component::MessageComponentInteractionData,
 		Interaction,
 	},
 };
@@ -15,38 +17,64 @@ use twilight_util::builder::command::{
 
 use crate::{
 	bot::{
-		action::CommandAck,
+		action::{CommandAck, TrackbearRetryTally},
 		context::{GenericResponse, GenericResponseData},
-		utils::command::{get_integer, get_string},
+		utils::{
+			action_row,
+			command::{get_integer, get_string},
+			time::parse_day_relative_to,
+			trackbear_client,
+		},
 		App,
 	},
-	db::{member::Member, project::Project, trackbear_login::TrackbearLogin},
+	db::{
+		member::Member, pending_tally::PendingTally, project::Project,
+		trackbear_login::TrackbearLogin, user_preference::UserPreference,
+	},
 	error_ext::ErrorExt,
+	trackbear::{
+		client::{is_transient, Measure},
+		project::TallyDetails,
+	},
 };
 
+pub mod tally;
+
 #[tracing::instrument]
 pub fn command() -> Result<Command> {
-	CommandBuilder::new("words", "TrackBear word tracking", CommandType::ChatInput)
-		.option(SubCommandBuilder::new(
-			"show",
-			"Show off your word count and any pretties",
-		))
-		.option(
-			SubCommandBuilder::new("project", "Set project by ID (or use /trackbear projects)")
-				.option(IntegerBuilder::new("id", "The TrackBear project ID").required(true)),
-		)
-		.option(
-			SubCommandBuilder::new("record", "Set your word count").option(
-				StringBuilder::new(
-					"words",
-					"New total word count, or relative using +/- prefixes",
-				)
-				.required(true),
+	tally::with_subcommands(
+		CommandBuilder::new("words", "TrackBear word tracking", CommandType::ChatInput)
+			.option(SubCommandBuilder::new(
+				"show",
+				"Show off your word count and any pretties",
+			))
+			.option(
+				SubCommandBuilder::new("project", "Set project by ID (or use /trackbear projects)")
+					.option(IntegerBuilder::new("id", "The TrackBear project ID").required(true)),
+			)
+			.option(
+				SubCommandBuilder::new("record", "Set your word count")
+					.option(
+						StringBuilder::new(
+							"words",
+							"New total word count, or relative using +/- prefixes",
+						)
+						.required(true),
+					)
+					.option(StringBuilder::new(
+						"date",
+						"Day to record on, like yesterday, monday or 2024-11-03 (defaults to today)",
+					))
+					.option(StringBuilder::new("note", "Note to add to the tally"))
+					.option(StringBuilder::new(
+						"tags",
+						"TrackBear tags, separated by commas",
+					)),
 			),
-		)
-		.validate()
-		.into_diagnostic()
-		.map(|cmd| cmd.build())
+	)
+	.validate()
+	.into_diagnostic()
+	.map(|cmd| cmd.build())
 }
 
 pub async fn on_command(
@@ -72,6 +100,15 @@ pub async fn on_command(
 		Some(("record", opts)) => record_words(app.clone(), interaction, opts)
 			.await
 			.wrap_err("command: record")?,
+		Some(("tallies", _opts)) => tally::list(app.clone(), interaction)
+			.await
+			.wrap_err("command: tallies")?,
+		Some(("tally-edit", opts)) => tally::edit(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: tally-edit")?,
+		Some(("tally-delete", opts)) => tally::delete(app.clone(), interaction, opts)
+			.await
+			.wrap_err("command: tally-delete")?,
 		Some((other, _)) => warn!("unhandled words subcommand: {other}"),
 		_ => error!("unreachable bare words command"),
 	}
@@ -79,6 +116,24 @@ pub async fn on_command(
 	Ok(())
 }
 
+pub async fn on_component(
+	app: App,
+	interaction: &Interaction,
+	subids: &[&str],
+	component_data: &MessageComponentInteractionData,
+) -> Result<()> {
+	debug!(?subids, ?component_data, "words component action");
+
+	match subids {
+		["undo", tally_id] => tally::undo(app.clone(), interaction, tally_id)
+			.await
+			.wrap_err("action: undo")?,
+		id => warn!(?id, "unhandled words component action"),
+	}
+
+	Ok(())
+}
+
 async fn show(app: App, interaction: &Interaction) -> Result<()> {
 	let member = Member::try_from(interaction)?;
 	app.do_action(CommandAck::new(interaction)).await.log().ok();
@@ -116,9 +171,7 @@ async fn set_project(
 		.ok();
 
 	let member = Member::try_from(interaction)?;
-	let client = TrackbearLogin::client_for_member(app.clone(), member)
-		.await?
-		.ok_or_else(|| miette!("You need to /trackbear login first!"))?;
+	let client = trackbear_client(app.clone(), member).await?;
 
 	debug!(
 		?project_id,
@@ -185,6 +238,32 @@ async fn record_words(
 	debug!(?words, "words record: parsed input");
 
 	let member = Member::try_from(interaction)?;
+	let date = match get_string(options, "date") {
+		Some(input) => {
+			let today = member_today(app.clone(), member).await?;
+			let date = parse_day_relative_to(today, input)?;
+			if date > today {
+				return Err(miette!("can't record words in the future"));
+			}
+			Some(date)
+		}
+		None => None,
+	};
+	let details = TallyDetails {
+		date,
+		note: get_string(options, "note")
+			.map(|note| note.trim().to_string())
+			.filter(|note| !note.is_empty()),
+		tags: get_string(options, "tags")
+			.map(|tags| {
+				tags.split(',')
+					.map(|tag| tag.trim().to_string())
+					.filter(|tag| !tag.is_empty())
+					.collect()
+			})
+			.unwrap_or_default(),
+	};
+
 	app.do_action(CommandAck::ephemeral(interaction))
 		.await
 		.log()
@@ -193,11 +272,42 @@ async fn record_words(
 	let project = Project::get_for_member(app.clone(), member)
 		.await?
 		.ok_or_else(|| miette!("no project set up! Use /words project"))?;
-	let client = TrackbearLogin::client_for_member(app.clone(), member)
+	// not validated, so that the words can still be queued if TrackBear is down
+	let client = TrackbearLogin::get_for_member(app.clone(), member)
 		.await?
+		.and_then(|login| login.client_unchecked(app.clone()))
 		.ok_or_else(|| miette!("You need to /trackbear login to be able to record words!"))?;
 
-	save_words(app, interaction, &client, &project, words).await
+	save_words(
+		app,
+		interaction,
+		&client,
+		&project,
+		Measure::Word,
+		words,
+		details,
+	)
+	.await
+}
+
+/// Reschedule the pending tallies whose retry was lost, like when the bot stopped mid-attempt.
+pub async fn load_from_db(app: App) -> Result<()> {
+	let pending = PendingTally::get_all_unscheduled(app.clone()).await?;
+	for tally in &pending {
+		TrackbearRetryTally::schedule(app.clone(), tally).await?;
+	}
+
+	info!(rescheduled = %pending.len(), "loaded pending tallies from db");
+
+	Ok(())
+}
+
+/// Today's date in the member's timezone.
+async fn member_today(app: App, member: Member) -> Result<NaiveDate> {
+	let tz = UserPreference::get_or_create(app, member)
+		.await?
+		.timezone_tz()?;
+	Ok(Utc::now().with_timezone(&tz).date_naive())
 }
 
 pub async fn save_words(
@@ -205,33 +315,75 @@ pub async fn save_words(
 	interaction: &Interaction,
 	client: &crate::trackbear::TrackbearClient,
 	project: &Project,
+	measure: Measure,
 	words: SaveWords,
+	mut details: TallyDetails,
 ) -> Result<()> {
-	let trackbear_project = crate::trackbear::Project::fetch(client, project.trackbear_id).await?;
+	let today = member_today(app.clone(), project.member).await?;
+	let date = *details.date.get_or_insert(today);
+	details.note = details
+		.note
+		.or_else(|| Some("Updated via sassbot".to_string()));
+
+	let request = details.into_request(
+		project.trackbear_id,
+		measure.clone(),
+		match words {
+			SaveWords::Absolute(n) => n as i64,
+			SaveWords::Relative(n) => n,
+		},
+		match words {
+			SaveWords::Absolute(_) => true,
+			SaveWords::Relative(_) => false,
+		},
+	);
 
 	debug!(?project.id, ?words, "posting new wordcount to TrackBear");
 
-	let tally = trackbear_project
-		.add_tally(
-			client,
-			match words {
-				SaveWords::Absolute(n) => n as i64,
-				SaveWords::Relative(n) => n,
-			},
-			match words {
-				SaveWords::Absolute(_) => true,
-				SaveWords::Relative(_) => false,
-			},
-			Some(format!("Updated via sassbot")),
-		)
-		.await?;
+	let tally = match client.create_tally(request.clone()).await {
+		Ok(tally) => tally,
+		Err(err) if is_transient(&err) => {
+			warn!(?project.id, ?err, "TrackBear unavailable, queueing tally");
+			let pending = PendingTally::create(app.clone(), project.member, &request).await?;
+			TrackbearRetryTally::schedule(app.clone(), &pending).await?;
+
+			return app
+				.send_response(GenericResponse::from_interaction(
+					interaction,
+					GenericResponseData {
+						content: Some(
+							"⏳ TrackBear isn't answering right now, so I'll keep trying to save this in the background and DM you once it's done."
+								.to_string(),
+						),
+						ephemeral: true,
+						..Default::default()
+					},
+				))
+				.await
+				.map(drop);
+		}
+		Err(err) => return Err(err),
+	};
 
 	debug!(?project.id, ?tally.id, "created tally on TrackBear");
 
 	app.send_response(GenericResponse::from_interaction(
 		interaction,
 		GenericResponseData {
-			content: Some("Updated your word count on TrackBear!".to_string()),
+			content: Some(format!(
+				"Updated your {} on TrackBear{}!",
+				if measure == Measure::Word {
+					"word count"
+				} else {
+					"progress"
+				},
+				if date == today {
+					String::new()
+				} else {
+					format!(" for {}", date.format("%A %-d %B"))
+				}
+			)),
+			components: action_row(vec![tally::undo_button(&tally)]),
 			ephemeral: true,
 			..Default::default()
 		},
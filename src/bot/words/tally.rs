@@ -0,0 +1,209 @@
+use std::{fmt::Write, str::FromStr};
+
+use chrono::NaiveDate;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use twilight_model::{
+	application::interaction::{application_command::CommandDataOption, Interaction},
+	channel::message::component::{Button, ButtonStyle, Component},
+};
+use twilight_util::builder::command::{
+	CommandBuilder, IntegerBuilder, StringBuilder, SubCommandBuilder,
+};
+
+use crate::{
+	bot::{
+		action::{CommandAck, ComponentAck},
+		utils::{
+			command::{get_integer, get_string},
+			respond_ephemeral, trackbear_client,
+		},
+		App,
+	},
+	db::{member::Member, project::Project},
+	error_ext::ErrorExt,
+	trackbear::client::{Tally, UpdateTallyRequest},
+};
+
+/// How many tallies `/words tallies` shows.
+const RECENT_TALLIES: usize = 10;
+
+/// Add the tally subcommands to the words command.
+pub fn with_subcommands(command: CommandBuilder) -> CommandBuilder {
+	command
+		.option(SubCommandBuilder::new(
+			"tallies",
+			"List the recent tallies of your current project",
+		))
+		.option(
+			SubCommandBuilder::new("tally-edit", "Fix a tally on TrackBear")
+				.option(tally_option())
+				.option(IntegerBuilder::new(
+					"count",
+					"How many words the tally should add, like 500 or -200",
+				))
+				.option(StringBuilder::new(
+					"date",
+					"Day the tally is for, like 2024-11-03",
+				))
+				.option(StringBuilder::new("note", "New note for the tally")),
+		)
+		.option(
+			SubCommandBuilder::new("tally-delete", "Remove a tally from TrackBear")
+				.option(tally_option()),
+		)
+}
+
+fn tally_option() -> IntegerBuilder {
+	IntegerBuilder::new("tally", "TrackBear tally ID, from /words tallies").required(true)
+}
+
+/// Button to remove a tally that was just created.
+pub fn undo_button(tally: &Tally) -> Component {
+	Component::Button(Button {
+		custom_id: Some(format!("words:undo:{}", tally.id)),
+		disabled: false,
+		emoji: None,
+		label: Some("Undo".to_string()),
+		style: ButtonStyle::Secondary,
+		url: None,
+		sku_id: None,
+	})
+}
+
+pub async fn list(app: App, interaction: &Interaction) -> Result<()> {
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let client = trackbear_client(app.clone(), member).await?;
+	let project = Project::get_for_member(app.clone(), member)
+		.await?
+		.ok_or_else(|| miette!("no project set up! Use /words project"))?;
+	let trackbear_project = crate::trackbear::Project::fetch(&client, project.trackbear_id).await?;
+	let tallies = trackbear_project
+		.recent_tallies(&client, RECENT_TALLIES)
+		.await?;
+
+	let content = if tallies.is_empty() {
+		format!(
+			"There aren't any tallies for **{}** yet!",
+			trackbear_project.title()
+		)
+	} else {
+		let mut content = format!("Recent tallies for **{}**:\n", trackbear_project.title());
+		for tally in &tallies {
+			writeln!(content, "- {}", describe(tally)).ok();
+		}
+		content
+	};
+
+	respond_ephemeral(app, interaction, content, Vec::new()).await
+}
+
+pub async fn edit(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let tally_id = get_integer(options, "tally").ok_or_else(|| miette!("tally is required"))?;
+	let count = get_integer(options, "count");
+	let date = get_string(options, "date")
+		.map(|date| {
+			NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+				.into_diagnostic()
+				.wrap_err_with(|| format!("{date} isn't a date like 2024-11-30"))
+		})
+		.transpose()?;
+	let note = get_string(options, "note").map(|note| note.trim().to_string());
+	if count.is_none() && date.is_none() && note.is_none() {
+		return Err(miette!("nothing to change"));
+	}
+
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	let client = trackbear_client(app.clone(), member).await?;
+	let tally = client
+		.update_tally(
+			tally_id,
+			UpdateTallyRequest {
+				date: date.map(|date| date.format("%Y-%m-%d").to_string()),
+				count,
+				note,
+			},
+		)
+		.await
+		.wrap_err("couldn't change the tally")?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("✏️ Updated {}", describe(&tally)),
+		Vec::new(),
+	)
+	.await
+}
+
+pub async fn delete(
+	app: App,
+	interaction: &Interaction,
+	options: &[CommandDataOption],
+) -> Result<()> {
+	let tally_id = get_integer(options, "tally").ok_or_else(|| miette!("tally is required"))?;
+
+	let member = Member::try_from(interaction)?;
+	app.do_action(CommandAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	remove(app, interaction, member, tally_id).await
+}
+
+/// Remove the tally from the Undo button of `/words record`.
+pub async fn undo(app: App, interaction: &Interaction, tally_id: &str) -> Result<()> {
+	let tally_id = i64::from_str(tally_id).into_diagnostic()?;
+	let member = Member::try_from(interaction)?;
+	app.do_action(ComponentAck::ephemeral(interaction))
+		.await
+		.log()
+		.ok();
+
+	remove(app, interaction, member, tally_id).await
+}
+
+async fn remove(app: App, interaction: &Interaction, member: Member, tally_id: i64) -> Result<()> {
+	let client = trackbear_client(app.clone(), member).await?;
+	let tally = client
+		.delete_tally(tally_id)
+		.await
+		.wrap_err("couldn't remove the tally")?;
+
+	respond_ephemeral(
+		app,
+		interaction,
+		format!("↩️ Removed {}", describe(&tally)),
+		Vec::new(),
+	)
+	.await
+}
+
+/// One line about the tally: its ID, day, count, and note.
+fn describe(tally: &Tally) -> String {
+	let mut line = format!(
+		"`{}` {}: {:+} {}",
+		tally.id,
+		tally.date,
+		tally.count,
+		tally.measure.plural()
+	);
+	if let Some(note) = tally.note.as_deref().filter(|note| !note.is_empty()) {
+		write!(line, " — _{note}_").ok();
+	}
+	line
+}
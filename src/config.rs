@@ -20,6 +20,9 @@ pub struct Config {
 	#[knuffel(child, default)]
 	pub internal: InternalConfig,
 
+	#[knuffel(child, default)]
+	pub trackbear: TrackbearConfig,
+
 	#[knuffel(child, unwrap(argument), default)]
 	pub nominare_url: Option<String>,
 }
@@ -279,3 +282,29 @@ impl Default for InternalConfig {
 		}
 	}
 }
+
+#[derive(Debug, Clone, knuffel::Decode)]
+pub struct TrackbearConfig {
+	#[knuffel(child, unwrap(argument), default = Self::default().base_url)]
+	pub base_url: String,
+
+	#[knuffel(child, unwrap(argument), default = Self::default().cache_ttl)]
+	pub cache_ttl: u64,
+
+	#[knuffel(child, unwrap(argument), default = Self::default().retries)]
+	pub retries: u32,
+
+	#[knuffel(child, unwrap(argument), default = Self::default().timeout)]
+	pub timeout: u64,
+}
+
+impl Default for TrackbearConfig {
+	fn default() -> Self {
+		Self {
+			base_url: "https://trackbear.app/api/v1".into(),
+			cache_ttl: 60,
+			retries: 3,
+			timeout: 10,
+		}
+	}
+}
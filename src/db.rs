@@ -1,9 +1,17 @@
 pub mod channel;
 pub mod error;
+pub mod export;
+pub mod guild_settings;
 pub mod member;
 pub mod message;
 pub mod migrate;
+pub mod pending_tally;
 pub mod project;
+pub mod scheduled_action;
 pub mod sprint;
+pub mod sprint_chain;
+pub mod sprint_notification;
+pub mod sprint_schedule;
+pub mod sprint_stats;
 pub mod trackbear_login;
 pub mod user_preference;
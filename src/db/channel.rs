@@ -1,5 +1,6 @@
 use miette::{miette, IntoDiagnostic, Report, Result};
 use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
 use twilight_mention::{fmt::MentionFormat, Mention};
 use twilight_model::{
 	application::interaction::Interaction,
@@ -13,7 +14,7 @@ use twilight_model::{
 
 use crate::bot::App;
 
-#[derive(Debug, Clone, Copy, ToSql, FromSql)]
+#[derive(Debug, Clone, Copy, ToSql, FromSql, Serialize, Deserialize)]
 #[postgres(name = "channel")]
 pub struct Channel {
 	pub guild_id: Option<i64>,
@@ -0,0 +1,256 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use miette::{miette, Context, IntoDiagnostic, Result};
+use pg_interval::Interval;
+use serde::Serialize;
+use tokio_postgres::{Client, Row};
+use uuid::Uuid;
+
+use crate::bot::utils::time::IntervalExt;
+
+use super::sprint::{SprintMeasure, SprintStatus};
+
+/// Which sprints and participants to export.
+///
+/// This takes a database client rather than the bot, so operators can also export from the CLI.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+	pub guild_id: Option<i64>,
+	pub user_id: Option<i64>,
+	pub since: Option<DateTime<Utc>>,
+	pub until: Option<DateTime<Utc>>,
+	/// Whether to include private sprints, which are otherwise only seen by their host.
+	pub include_private: bool,
+}
+
+impl ExportFilter {
+	/// Set the date range from `YYYY-MM-DD` dates, both inclusive and in UTC.
+	pub fn with_dates(mut self, since: Option<&str>, until: Option<&str>) -> Result<Self> {
+		let parse = |date: &str| {
+			NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+				.into_diagnostic()
+				.wrap_err_with(|| format!("{date} isn't a date like 2024-11-30"))
+				.map(|date| date.and_time(Default::default()).and_utc())
+		};
+
+		self.since = since.map(parse).transpose()?;
+		self.until = until
+			.map(|date| {
+				parse(date)?
+					.checked_add_signed(Duration::days(1))
+					.ok_or_else(|| miette!("{date} is too far in the future"))
+			})
+			.transpose()?;
+		if let (Some(since), Some(until)) = (self.since, self.until) {
+			if since >= until {
+				return Err(miette!("the start of the range must be before its end"));
+			}
+		}
+
+		Ok(self)
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+	#[default]
+	Csv,
+	Json,
+}
+
+impl ExportFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			Self::Csv => "csv",
+			Self::Json => "json",
+		}
+	}
+}
+
+impl FromStr for ExportFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"csv" => Ok(Self::Csv),
+			"json" => Ok(Self::Json),
+			other => Err(format!("unknown export format: {other}")),
+		}
+	}
+}
+
+impl fmt::Display for ExportFormat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.extension())
+	}
+}
+
+/// One participant of one sprint.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRow {
+	pub sprint_id: Uuid,
+	pub sprint: i32,
+	pub guild_id: Option<i64>,
+	pub channel_id: Option<i64>,
+	pub starting_at: DateTime<Utc>,
+	pub duration_minutes: i64,
+	pub measure: &'static str,
+	pub status: String,
+	pub chain_round: Option<i32>,
+	pub user_id: i64,
+	pub team: Option<String>,
+	pub goal: Option<i32>,
+	pub words_start: Option<i32>,
+	pub words_end: Option<i32>,
+	pub words_written: Option<i32>,
+}
+
+impl ExportRow {
+	fn from_row(row: Row) -> Result<Self> {
+		let duration: Interval = row.try_get("duration").into_diagnostic()?;
+		let measure: SprintMeasure = row.try_get("measure").into_diagnostic()?;
+		let status: SprintStatus = row.try_get("status").into_diagnostic()?;
+		let words_start: Option<i32> = row.try_get("words_start").into_diagnostic()?;
+		let words_end: Option<i32> = row.try_get("words_end").into_diagnostic()?;
+
+		Ok(Self {
+			sprint_id: row.try_get("id").into_diagnostic()?,
+			sprint: row.try_get("shortid").into_diagnostic()?,
+			guild_id: row.try_get("guild_id").into_diagnostic()?,
+			channel_id: row.try_get("channel_id").into_diagnostic()?,
+			starting_at: row.try_get("starting_at").into_diagnostic()?,
+			duration_minutes: duration.to_duration().num_minutes(),
+			measure: measure.plural(),
+			status: format!("{status:?}"),
+			chain_round: row.try_get("chain_round").into_diagnostic()?,
+			user_id: row.try_get("user_id").into_diagnostic()?,
+			team: row.try_get("team").into_diagnostic()?,
+			goal: row.try_get("goal").into_diagnostic()?,
+			words_start,
+			words_end,
+			words_written: words_start.zip(words_end).map(|(start, end)| end - start),
+		})
+	}
+
+	/// Participants of sprints which weren't cancelled, oldest first.
+	#[tracing::instrument(skip(db))]
+	pub async fn query(db: &Client, filter: &ExportFilter) -> Result<Vec<Self>> {
+		db.query(
+			"
+			SELECT
+				s.id, s.shortid, s.starting_at, s.duration, s.measure, s.status, s.chain_round,
+				(s.channel).guild_id AS guild_id, (s.channel).channel_id AS channel_id,
+				(p.member).user_id AS user_id, p.team, p.goal, p.words_start, p.words_end
+			FROM sprints s
+			JOIN sprint_participants p ON p.sprint_id = s.id
+			WHERE s.cancelled_at IS NULL
+				AND ($1::bigint IS NULL OR (s.channel).guild_id = $1)
+				AND ($2::bigint IS NULL OR (p.member).user_id = $2)
+				AND ($3::timestamptz IS NULL OR s.starting_at >= $3)
+				AND ($4::timestamptz IS NULL OR s.starting_at < $4)
+				AND ($5 OR NOT s.private)
+			ORDER BY s.starting_at, s.shortid, (p.member).user_id
+			",
+			&[
+				&filter.guild_id,
+				&filter.user_id,
+				&filter.since,
+				&filter.until,
+				&filter.include_private,
+			],
+		)
+		.await
+		.into_diagnostic()
+		.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+		.wrap_err("db: export sprints")
+	}
+
+	const CSV_HEADER: &'static str = "sprint_id,sprint,guild_id,channel_id,starting_at,duration_minutes,measure,status,chain_round,user_id,team,goal,words_start,words_end,words_written";
+
+	fn csv_line(&self) -> String {
+		fn opt<T: ToString>(value: Option<T>) -> String {
+			value.map_or(String::new(), |v| v.to_string())
+		}
+
+		[
+			self.sprint_id.to_string(),
+			self.sprint.to_string(),
+			opt(self.guild_id),
+			opt(self.channel_id),
+			self.starting_at.to_rfc3339(),
+			self.duration_minutes.to_string(),
+			self.measure.to_string(),
+			self.status.clone(),
+			opt(self.chain_round),
+			self.user_id.to_string(),
+			csv_escape(self.team.as_deref().unwrap_or_default()),
+			opt(self.goal),
+			opt(self.words_start),
+			opt(self.words_end),
+			opt(self.words_written),
+		]
+		.join(",")
+	}
+}
+
+/// Quote a CSV field if it needs it.
+///
+/// Fields which a spreadsheet would read as a formula are prefixed with `'` so they stay text.
+fn csv_escape(field: &str) -> String {
+	if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+		format!("\"'{}\"", field.replace('"', "\"\""))
+	} else if field.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_string()
+	}
+}
+
+/// Encode the rows as a file in the format.
+pub fn encode(rows: &[ExportRow], format: ExportFormat) -> Result<Vec<u8>> {
+	match format {
+		ExportFormat::Csv => {
+			let mut csv = String::from(ExportRow::CSV_HEADER);
+			csv.push_str("\r\n");
+			for row in rows {
+				csv.push_str(&row.csv_line());
+				csv.push_str("\r\n");
+			}
+			Ok(csv.into_bytes())
+		}
+		ExportFormat::Json => serde_json::to_vec_pretty(rows).into_diagnostic(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn csv_quotes_only_when_needed() {
+		assert_eq!(csv_escape("Red"), "Red");
+		assert_eq!(csv_escape("Red, Blue"), "\"Red, Blue\"");
+		assert_eq!(csv_escape("the \"best\""), "\"the \"\"best\"\"\"");
+		assert_eq!(csv_escape("=SUM(A1)"), "\"'=SUM(A1)\"");
+		assert_eq!(csv_escape("-team"), "\"'-team\"");
+	}
+
+	#[test]
+	fn dates_are_inclusive() {
+		let filter = ExportFilter::default()
+			.with_dates(Some("2024-11-01"), Some("2024-11-30"))
+			.unwrap();
+		assert_eq!(
+			filter.since.unwrap().to_rfc3339(),
+			"2024-11-01T00:00:00+00:00"
+		);
+		assert_eq!(
+			filter.until.unwrap().to_rfc3339(),
+			"2024-12-01T00:00:00+00:00"
+		);
+		assert!(ExportFilter::default()
+			.with_dates(Some("2024-11-30"), Some("2024-11-01"))
+			.is_err());
+	}
+}
@@ -0,0 +1,129 @@
+use chrono::Duration;
+use miette::{Context, IntoDiagnostic, Result};
+use tokio_postgres::Row;
+use tracing::debug;
+use twilight_mention::Mention;
+use twilight_model::id::{marker::RoleMarker, Id};
+
+use crate::bot::App;
+
+/// Settings which apply to a whole guild, changed by its admins.
+#[derive(Debug, Clone)]
+pub struct GuildSettings {
+	pub guild_id: i64,
+	pub chat_words: bool,
+	/// Default duration of new sprints, in minutes.
+	pub sprint_duration: i32,
+	/// Default start time of new sprints, like `15m` or `08:30`.
+	pub sprint_when: String,
+	/// How long before the start and the end of sprints to warn, in seconds; 0 for never.
+	pub sprint_warning: i32,
+	/// Whether "write" and "sprint" are more likely to be chosen by `/choose`.
+	pub choose_weighting: bool,
+	/// Role to mention when sprints are announced and about to start.
+	pub sprinters_role: Option<i64>,
+	/// How long after sprints end to wait for ending words before summarizing anyway, in minutes.
+	pub summary_grace: i32,
+}
+
+impl Default for GuildSettings {
+	fn default() -> Self {
+		Self {
+			guild_id: 0,
+			chat_words: false,
+			sprint_duration: 20,
+			sprint_when: "15m".into(),
+			sprint_warning: 30,
+			choose_weighting: true,
+			sprinters_role: None,
+			summary_grace: 10,
+		}
+	}
+}
+
+impl GuildSettings {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			guild_id: row.try_get("guild_id").into_diagnostic()?,
+			chat_words: row.try_get("chat_words").into_diagnostic()?,
+			sprint_duration: row.try_get("sprint_duration").into_diagnostic()?,
+			sprint_when: row.try_get("sprint_when").into_diagnostic()?,
+			sprint_warning: row.try_get("sprint_warning").into_diagnostic()?,
+			choose_weighting: row.try_get("choose_weighting").into_diagnostic()?,
+			sprinters_role: row.try_get("sprinters_role").into_diagnostic()?,
+			summary_grace: row.try_get("summary_grace").into_diagnostic()?,
+		})
+	}
+
+	/// Get the settings for a guild, or the defaults if none were ever set
+	#[tracing::instrument(skip(app))]
+	pub async fn get(app: App, guild_id: i64) -> Result<Self> {
+		app.db
+			.query_opt(
+				"SELECT * FROM guild_settings WHERE guild_id = $1",
+				&[&guild_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|row| row.map(Self::from_row).transpose())
+			.wrap_err("db: get guild settings")
+			.map(|settings| {
+				settings.unwrap_or_else(|| Self {
+					guild_id,
+					..Default::default()
+				})
+			})
+	}
+
+	/// Save all the settings of the guild
+	#[tracing::instrument(skip(app))]
+	pub async fn save(&self, app: App) -> Result<()> {
+		debug!(?self.guild_id, "saving guild settings");
+
+		app.db
+			.execute(
+				"
+				INSERT INTO guild_settings (guild_id, chat_words, sprint_duration, sprint_when, sprint_warning, choose_weighting, sprinters_role, summary_grace)
+				VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+				ON CONFLICT (guild_id) DO UPDATE SET
+					chat_words = EXCLUDED.chat_words,
+					sprint_duration = EXCLUDED.sprint_duration,
+					sprint_when = EXCLUDED.sprint_when,
+					sprint_warning = EXCLUDED.sprint_warning,
+					choose_weighting = EXCLUDED.choose_weighting,
+					sprinters_role = EXCLUDED.sprinters_role,
+					summary_grace = EXCLUDED.summary_grace
+				",
+				&[
+					&self.guild_id,
+					&self.chat_words,
+					&self.sprint_duration,
+					&self.sprint_when,
+					&self.sprint_warning,
+					&self.choose_weighting,
+					&self.sprinters_role,
+					&self.summary_grace,
+				],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: save guild settings")
+			.map(drop)
+	}
+
+	/// How long before the start and the end of sprints to warn, if at all
+	pub fn sprint_warning(&self) -> Option<Duration> {
+		(self.sprint_warning > 0).then(|| Duration::seconds(self.sprint_warning.into()))
+	}
+
+	/// How long after sprints end to wait for ending words before summarizing anyway
+	pub fn summary_grace(&self) -> Duration {
+		Duration::minutes(self.summary_grace.into())
+	}
+
+	/// Mention of the sprinters role, if one is set
+	pub fn sprinters_mention(&self) -> Option<String> {
+		self.sprinters_role
+			.map(|id| Id::<RoleMarker>::new(id as _).mention().to_string())
+	}
+}
@@ -1,8 +1,11 @@
-use miette::{miette, IntoDiagnostic, Report, Result};
+use miette::{miette, Context, IntoDiagnostic, Report, Result};
 use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+use twilight_http::error::ErrorType;
 use twilight_mention::{fmt::MentionFormat, Mention};
 use twilight_model::{
 	application::interaction::Interaction,
+	channel::message::Message as DiscordMessage,
 	guild::Member as DiscordMember,
 	id::{
 		marker::{GuildMarker, UserMarker},
@@ -17,7 +20,7 @@ use crate::bot::App;
 // schema or we're 10k years in the future) reach even 60 bits of length
 // so we're quite safe casting them to i64
 
-#[derive(Debug, Clone, Copy, ToSql, FromSql)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql, Serialize, Deserialize)]
 #[postgres(name = "member")]
 pub struct Member {
 	pub guild_id: i64,
@@ -39,6 +42,20 @@ impl Member {
 		let member = self.to_member(app).await?;
 		Ok(member.nick.unwrap_or(member.user.name))
 	}
+
+	/// Their name, or None if they're not in the guild anymore.
+	pub async fn name_if_present(self, app: App) -> Result<Option<String>> {
+		match app.client.guild_member(self.into(), self.into()).await {
+			Ok(resp) => {
+				let member = resp.model().await.into_diagnostic()?;
+				Ok(Some(member.nick.unwrap_or(member.user.name)))
+			}
+			Err(err) => match err.kind() {
+				ErrorType::Response { status, .. } if status.get() == 404 => Ok(None),
+				_ => Err(err).into_diagnostic().wrap_err("get guild member"),
+			},
+		}
+	}
 }
 
 impl Mention<Id<UserMarker>> for Member {
@@ -94,3 +111,15 @@ impl TryFrom<&Interaction> for Member {
 		})
 	}
 }
+
+impl TryFrom<&DiscordMessage> for Member {
+	type Error = Report;
+
+	fn try_from(msg: &DiscordMessage) -> Result<Self> {
+		let guild_id = msg.guild_id.ok_or(miette!("message is not from a guild"))?;
+		Ok(Self {
+			guild_id: guild_id.get() as _,
+			user_id: msg.author.id.get() as _,
+		})
+	}
+}
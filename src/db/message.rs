@@ -1,5 +1,6 @@
 use miette::{Context, Report, Result};
 use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
 use twilight_model::{
 	channel::message::Message as DiscordMessage,
 	id::{
@@ -10,13 +11,28 @@ use twilight_model::{
 
 use super::channel::Channel;
 
-#[derive(Debug, Clone, Copy, ToSql, FromSql)]
+#[derive(Debug, Clone, Copy, ToSql, FromSql, Serialize, Deserialize)]
 #[postgres(name = "message")]
 pub struct Message {
 	pub channel: Channel,
 	pub message_id: i64,
 }
 
+impl Message {
+	/// Link to the message, to jump to it from anywhere in Discord.
+	pub fn link(&self) -> String {
+		format!(
+			"https://discord.com/channels/{guild}/{channel}/{message}",
+			guild = self
+				.channel
+				.guild_id
+				.map_or_else(|| "@me".to_string(), |id| id.to_string()),
+			channel = self.channel.channel_id,
+			message = self.message_id,
+		)
+	}
+}
+
 impl From<Message> for Id<MessageMarker> {
 	fn from(msg: Message) -> Self {
 		Id::new(msg.message_id as _)
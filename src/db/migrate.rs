@@ -22,6 +22,26 @@ const MIGRATIONS: &[(&str, &str)] = &[
 	migration!("007_rename_nano_id_to_trackbear_id"),
 	migration!("008_drop_project_goals"),
 	migration!("009_user_preferences"),
+	migration!("010_sprint_channels"),
+	migration!("011_sprint_schedules"),
+	migration!("012_sprint_chains"),
+	migration!("013_sprint_revision"),
+	migration!("014_scheduled_actions"),
+	migration!("015_leaderboard_opt_out"),
+	migration!("016_sprint_teams"),
+	migration!("017_sprint_goals"),
+	migration!("018_sprint_measures"),
+	migration!("019_guild_settings"),
+	migration!("020_carry_words"),
+	migration!("021_guild_settings_defaults"),
+	migration!("022_sprint_notifications"),
+	migration!("023_sprint_reminders"),
+	migration!("024_sprint_close_out"),
+	migration!("025_sprint_hosts"),
+	migration!("026_private_sprints"),
+	migration!("027_pending_tallies"),
+	migration!("028_scheduled_action_claims"),
+	migration!("029_carried_words"),
 ];
 
 #[cfg(debug_assertions)]
@@ -0,0 +1,115 @@
+use miette::{Context, IntoDiagnostic, Result};
+use serde_json::Value;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::{bot::App, trackbear::client::CreateTallyRequest};
+
+use super::member::Member;
+
+/// A tally which couldn't be saved on TrackBear yet, to be retried in the background.
+#[derive(Debug, Clone)]
+pub struct PendingTally {
+	pub id: Uuid,
+	pub member: Member,
+	pub request: CreateTallyRequest,
+	/// How many times saving it was retried.
+	pub attempts: i32,
+}
+
+impl PendingTally {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			id: row.try_get("id").into_diagnostic()?,
+			member: row.try_get("member").into_diagnostic()?,
+			request: row
+				.try_get::<_, Value>("request")
+				.into_diagnostic()
+				.and_then(|request| serde_json::from_value(request).into_diagnostic())
+				.wrap_err("deserialize tally request")?,
+			attempts: row.try_get("attempts").into_diagnostic()?,
+		})
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn create(app: App, member: Member, request: &CreateTallyRequest) -> Result<Self> {
+		let request = serde_json::to_value(request)
+			.into_diagnostic()
+			.wrap_err("serialize tally request")?;
+
+		app.db
+			.query_one(
+				"INSERT INTO pending_tallies (member, request) VALUES ($1, $2) RETURNING *",
+				&[&member, &request],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: create pending tally")
+	}
+
+	/// Get a pending tally, if it hasn't been saved or given up on since.
+	#[tracing::instrument(skip(app))]
+	pub async fn get(app: App, id: Uuid) -> Result<Option<Self>> {
+		app.db
+			.query_opt("SELECT * FROM pending_tallies WHERE id = $1", &[&id])
+			.await
+			.into_diagnostic()
+			.and_then(|row| row.map(Self::from_row).transpose())
+			.wrap_err("db: get pending tally")
+	}
+
+	/// Get all the pending tallies which don't have a retry scheduled.
+	///
+	/// That's only the case if the bot stopped in the middle of an attempt.
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_unscheduled(app: App) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"
+				SELECT * FROM pending_tallies
+				WHERE NOT EXISTS (
+					SELECT FROM scheduled_actions
+					-- see TrackbearRetryTally::timer_key
+					WHERE key = 'pending-tally:' || pending_tallies.id
+				)
+				",
+				&[],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get unscheduled pending tallies")
+	}
+
+	/// Record a failed attempt at saving the tally.
+	#[tracing::instrument(skip(app))]
+	pub async fn failed(&mut self, app: App, error: &str) -> Result<()> {
+		self.attempts += 1;
+		app.db
+			.execute(
+				"
+				UPDATE pending_tallies SET
+					attempts = $2,
+					last_error = $3,
+					updated_at = CURRENT_TIMESTAMP
+				WHERE id = $1
+				",
+				&[&self.id, &self.attempts, &error],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: record pending tally attempt")
+			.map(drop)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn delete(self, app: App) -> Result<()> {
+		app.db
+			.execute("DELETE FROM pending_tallies WHERE id = $1", &[&self.id])
+			.await
+			.into_diagnostic()
+			.wrap_err("db: delete pending tally")
+			.map(drop)
+	}
+}
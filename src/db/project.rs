@@ -112,10 +112,15 @@ impl Project {
 				miette::miette!("No TrackBear login found. Use /trackbear login first.")
 			})?;
 
-		let client = login.client().await?;
+		let client = login.client(app).await?;
 		TrackbearProject::fetch(&client, self.trackbear_id).await
 	}
 
+	/// The project's current total on TrackBear, including its starting balance.
+	pub async fn word_count(&self, app: App) -> Result<i64> {
+		Ok(self.fetch(app).await?.word_count())
+	}
+
 	pub async fn show_text(&self, app: App) -> Result<String> {
 		let proj = self.fetch(app.clone()).await?;
 		let title = proj.title();
@@ -143,13 +148,13 @@ impl Project {
 				}
 			}
 
-			if goal.parameters.threshold.count != 50_000 {
-				write!(
-					deets,
-					", {} goal",
-					format_count(goal.parameters.threshold.count)
-				)
-				.ok();
+			if let Some(threshold) = goal
+				.parameters
+				.threshold
+				.as_ref()
+				.filter(|threshold| threshold.count != 50_000)
+			{
+				write!(deets, ", {} goal", format_count(threshold.count)).ok();
 			}
 		} else {
 			write!(deets, "no goal").ok();
@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use miette::{Context, IntoDiagnostic, Result};
+use serde_json::Value;
+use tokio_postgres::Row;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::bot::{action::Action, context::Timer, App};
+
+/// An action persisted to run at some later time.
+#[derive(Debug, Clone)]
+pub struct ScheduledAction {
+	pub id: Uuid,
+	pub key: Option<String>,
+	pub due_at: DateTime<Utc>,
+	pub action: Action,
+	/// When the ticker picked this up to run it, if it has.
+	pub claimed_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduledAction {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			id: row.try_get("id").into_diagnostic()?,
+			key: row.try_get("key").into_diagnostic()?,
+			due_at: row.try_get("due_at").into_diagnostic()?,
+			action: row
+				.try_get::<_, Value>("action")
+				.into_diagnostic()
+				.and_then(|action| serde_json::from_value(action).into_diagnostic())
+				.wrap_err("deserialize action")?,
+			claimed_at: row.try_get("claimed_at").into_diagnostic()?,
+		})
+	}
+
+	/// Rows which can't be read back (e.g. an action from an older version) are logged and dropped.
+	fn from_rows(rows: Vec<Row>) -> Vec<Self> {
+		rows.into_iter()
+			.filter_map(|row| {
+				Self::from_row(row)
+					.map_err(|err| error!("dropping unreadable scheduled action: {err:?}"))
+					.ok()
+			})
+			.collect()
+	}
+
+	/// Persist a timer.
+	///
+	/// If the timer has a key and there's already one with that key, it's replaced.
+	#[tracing::instrument(skip(app))]
+	pub async fn schedule(app: App, timer: Timer) -> Result<()> {
+		let action = serde_json::to_value(&timer.payload)
+			.into_diagnostic()
+			.wrap_err("serialize action")?;
+
+		app.db
+			.query(
+				"
+				INSERT INTO scheduled_actions (key, due_at, action)
+				VALUES ($1, $2, $3)
+				ON CONFLICT (key) DO UPDATE SET
+					due_at = EXCLUDED.due_at,
+					action = EXCLUDED.action,
+					created_at = CURRENT_TIMESTAMP,
+					claimed_at = NULL
+				",
+				&[&timer.key, &timer.until, &action],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: schedule action")
+			.map(drop)
+	}
+
+	/// Claim and return all the actions that are due, earliest first.
+	///
+	/// They stay in the queue until they're marked [done](Self::done), so that any which were
+	/// claimed but didn't get to run before a restart are picked up again.
+	#[tracing::instrument(skip(app))]
+	pub async fn claim_due(app: App) -> Result<Vec<Self>> {
+		let rows = app
+			.db
+			.query(
+				"
+				UPDATE scheduled_actions SET claimed_at = CURRENT_TIMESTAMP
+				WHERE due_at <= CURRENT_TIMESTAMP AND claimed_at IS NULL
+				RETURNING *
+				",
+				&[],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: claim due actions")?;
+
+		let mut due = Vec::with_capacity(rows.len());
+		for row in rows {
+			let id: Uuid = row.try_get("id").into_diagnostic()?;
+			match Self::from_row(row) {
+				Ok(scheduled) => due.push(scheduled),
+				Err(err) => {
+					// it'll never be readable, so don't leave it claimed forever
+					error!("dropping unreadable scheduled action: {err:?}");
+					app.db
+						.query("DELETE FROM scheduled_actions WHERE id = $1", &[&id])
+						.await
+						.into_diagnostic()
+						.wrap_err("db: drop unreadable action")?;
+				}
+			}
+		}
+		due.sort_by_key(|scheduled| scheduled.due_at);
+		Ok(due)
+	}
+
+	/// Remove the action from the queue once it's run.
+	///
+	/// If it was rescheduled while it ran, the new one is kept.
+	#[tracing::instrument(skip(app))]
+	pub async fn done(&self, app: App) -> Result<()> {
+		app.db
+			.query(
+				"DELETE FROM scheduled_actions WHERE id = $1 AND claimed_at = $2",
+				&[&self.id, &self.claimed_at],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: remove done action")
+			.map(drop)
+	}
+
+	/// Release the claims left over from a previous run, so those actions go again.
+	#[tracing::instrument(skip(app))]
+	pub async fn release_claims(app: App) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE scheduled_actions SET claimed_at = NULL WHERE claimed_at IS NOT NULL",
+				&[],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: release claimed actions")
+			.map(drop)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn next_due_at(app: App) -> Result<Option<DateTime<Utc>>> {
+		app.db
+			.query_one(
+				"SELECT min(due_at) AS due_at FROM scheduled_actions WHERE claimed_at IS NULL",
+				&[],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|row| row.try_get("due_at").into_diagnostic())
+			.wrap_err("db: get next due action")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all(app: App) -> Result<Vec<Self>> {
+		app.db
+			.query("SELECT * FROM scheduled_actions ORDER BY due_at", &[])
+			.await
+			.into_diagnostic()
+			.map(Self::from_rows)
+			.wrap_err("db: get all scheduled actions")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn cancel(app: App, key: &str) -> Result<()> {
+		app.db
+			.query("DELETE FROM scheduled_actions WHERE key = $1", &[&key])
+			.await
+			.into_diagnostic()
+			.wrap_err("db: cancel scheduled action")
+			.map(drop)
+	}
+
+	/// Cancel all the actions with keys starting with this prefix.
+	#[tracing::instrument(skip(app))]
+	pub async fn cancel_prefix(app: App, prefix: &str) -> Result<()> {
+		app.db
+			.query(
+				"DELETE FROM scheduled_actions WHERE starts_with(key, $1)",
+				&[&prefix],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: cancel scheduled actions by prefix")
+			.map(drop)
+	}
+}
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use chrono::{DateTime, Duration, TimeZone, Utc};
 use futures_util::future::try_join_all;
@@ -13,12 +13,24 @@ use twilight_mention::{fmt::MentionFormat, Mention};
 use twilight_model::id::{marker::UserMarker, Id};
 use uuid::Uuid;
 
-use crate::bot::{
-	utils::time::{ChronoDateTimeExt, ChronoDurationExt},
-	App,
+use crate::{
+	bot::{
+		utils::{
+			card::{SummaryCard, SummaryCardRow},
+			pretties::Effect,
+			ranks,
+			time::{ChronoDateTimeExt, ChronoDurationExt, IntervalExt},
+		},
+		App,
+	},
+	error_ext::ErrorExt,
+	trackbear::client::Measure,
 };
 
-use super::{member::Member, message::Message};
+use super::{
+	channel::Channel, guild_settings::GuildSettings, member::Member, message::Message,
+	project::Project, sprint_chain::SprintChain, user_preference::UserPreference,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSql, FromSql)]
 #[postgres(name = "sprint_status")]
@@ -30,6 +42,59 @@ pub enum SprintStatus {
 	Summaried,
 }
 
+/// What a sprint counts, in the participants' starting and ending counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "sprint_measure")]
+pub enum SprintMeasure {
+	Word,
+	Page,
+	Chapter,
+	Scene,
+	Line,
+}
+
+impl SprintMeasure {
+	pub fn plural(self) -> &'static str {
+		match self {
+			Self::Word => "words",
+			Self::Page => "pages",
+			Self::Chapter => "chapters",
+			Self::Scene => "scenes",
+			Self::Line => "lines",
+		}
+	}
+
+	/// Rate of progress over a sprint of that many minutes.
+	///
+	/// Words are counted per minute, everything else is slower so it's per hour.
+	pub fn rate(self, count: i32, minutes: i64) -> f64 {
+		let rate = (count as f64) / (minutes as f64);
+		match self {
+			Self::Word => rate,
+			_ => rate * 60.0,
+		}
+	}
+
+	pub fn rate_unit(self) -> String {
+		match self {
+			Self::Word => "words per minute".into(),
+			other => format!("{} per hour", other.plural()),
+		}
+	}
+}
+
+impl From<SprintMeasure> for Measure {
+	fn from(measure: SprintMeasure) -> Self {
+		match measure {
+			SprintMeasure::Word => Self::Word,
+			SprintMeasure::Page => Self::Page,
+			SprintMeasure::Chapter => Self::Chapter,
+			SprintMeasure::Scene => Self::Scene,
+			SprintMeasure::Line => Self::Line,
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct Participant {
 	pub sprint_id: Uuid,
@@ -37,6 +102,10 @@ pub struct Participant {
 	pub joined_at: DateTime<Utc>,
 	pub words_start: Option<i32>,
 	pub words_end: Option<i32>,
+	pub team: Option<String>,
+	pub goal: Option<i32>,
+	/// Whether the starting words were carried from an earlier sprint.
+	pub words_start_carried: bool,
 }
 
 impl Mention<Id<UserMarker>> for Participant {
@@ -53,9 +122,19 @@ impl Participant {
 			joined_at: row.try_get("joined_at").into_diagnostic()?,
 			words_start: row.try_get("words_start").into_diagnostic()?,
 			words_end: row.try_get("words_end").into_diagnostic()?,
+			team: row.try_get("team").into_diagnostic()?,
+			goal: row.try_get("goal").into_diagnostic()?,
+			words_start_carried: row.try_get("words_start_carried").into_diagnostic()?,
 		})
 	}
 
+	/// What the starting words should become once the previous round's ending words are in.
+	///
+	/// Only carried words are replaced, as they were carried before that ending count existed.
+	fn start_after_previous_round(&self, previous_end: i32) -> Option<i32> {
+		self.words_start_carried.then_some(previous_end)
+	}
+
 	pub fn words_written(&self) -> Option<i32> {
 		if let (Some(start), Some(end)) = (self.words_start, self.words_end) {
 			Some(end - start)
@@ -65,6 +144,47 @@ impl Participant {
 	}
 }
 
+/// One participant's line in a sprint summary.
+#[derive(Debug)]
+struct SummaryLine {
+	name: String,
+	measure: SprintMeasure,
+	words: i32,
+	wpm: f64,
+	team: Option<String>,
+	goal: Option<i32>,
+	/// Whether they gave their ending words.
+	counted: bool,
+}
+
+impl fmt::Display for SummaryLine {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if !self.counted {
+			return write!(f, "_{}_: no count", self.name.replace('_', "\\_"));
+		}
+
+		write!(
+			f,
+			"_{name}_: **{words}** {plural} (**{wpm:.1}** {rate_unit})",
+			name = self.name.replace('_', "\\_"),
+			words = self.words,
+			plural = self.measure.plural(),
+			wpm = self.wpm,
+			rate_unit = self.measure.rate_unit(),
+		)?;
+
+		match self.goal {
+			Some(goal) if self.words >= goal => write!(f, " 🎯 {goal}"),
+			Some(goal) => write!(
+				f,
+				" — {}% of {goal}",
+				(self.words.max(0) as i64 * 100) / goal as i64
+			),
+			None => Ok(()),
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct Sprint {
 	pub id: Uuid,
@@ -75,8 +195,18 @@ pub struct Sprint {
 	pub starting_at: DateTime<Utc>,
 	pub duration: Interval,
 	pub status: SprintStatus,
-	pub interaction_token: String,
+	pub interaction_token: Option<String>,
 	pub announce: Option<Message>,
+	pub channel: Option<Channel>,
+	pub chain_id: Option<Uuid>,
+	pub chain_round: Option<i32>,
+	pub revision: i32,
+	pub teams: Option<Vec<String>>,
+	pub measure: SprintMeasure,
+	/// Who created the sprint, if it was created since hosts were recorded.
+	pub host: Option<Member>,
+	/// Only shown to the host, and kept out of listings for others.
+	pub private: bool,
 }
 
 impl Sprint {
@@ -92,16 +222,26 @@ impl Sprint {
 			status: row.try_get("status").into_diagnostic()?,
 			interaction_token: row.try_get("interaction_token").into_diagnostic()?,
 			announce: row.try_get("announce").into_diagnostic()?,
+			channel: row.try_get("channel").into_diagnostic()?,
+			chain_id: row.try_get("chain_id").into_diagnostic()?,
+			chain_round: row.try_get("chain_round").into_diagnostic()?,
+			revision: row.try_get("revision").into_diagnostic()?,
+			teams: row.try_get("teams").into_diagnostic()?,
+			measure: row.try_get("measure").into_diagnostic()?,
+			host: row.try_get("host").into_diagnostic()?,
+			private: row.try_get("private").into_diagnostic()?,
 		})
 	}
 
+	/// Record a new sprint, with its host (if it has one) as its first participant.
 	#[tracing::instrument(skip(app))]
 	pub async fn create<TZ>(
 		app: App,
 		starting_at: DateTime<TZ>,
 		duration: Duration,
-		interaction_token: &str,
-		member: Member,
+		interaction_token: Option<&str>,
+		channel: Channel,
+		host: Option<Member>,
 	) -> Result<Self>
 	where
 		TZ: TimeZone,
@@ -109,12 +249,14 @@ impl Sprint {
 		let sprint = app
 			.db
 			.query_one(
-				"INSERT INTO sprints (starting_at, duration, interaction_token) VALUES ($1, $2, $3) RETURNING *",
+				"INSERT INTO sprints (starting_at, duration, interaction_token, channel, host) VALUES ($1, $2, $3, $4, $5) RETURNING *",
 				&[
 					&starting_at.with_timezone(&Utc),
 					&Interval::from_duration(duration)
 						.ok_or(miette!("could not convert duration to interval"))?,
 					&interaction_token,
+					&channel,
+					&host,
 				],
 			)
 			.await
@@ -122,7 +264,9 @@ impl Sprint {
 			.and_then(Self::from_row)
 			.wrap_err("db: create sprint")?;
 
-		sprint.join(app, member).await?;
+		if let Some(host) = host {
+			sprint.join(app, host).await?;
+		}
 		Ok(sprint)
 	}
 
@@ -137,9 +281,12 @@ impl Sprint {
 	}
 
 	#[tracing::instrument(skip(app))]
-	pub async fn get_from_shortid(app: App, shortid: i32) -> Result<Self> {
+	pub async fn get_from_shortid(app: App, guild_id: i64, shortid: i32) -> Result<Self> {
 		app.db
-			.query_one("SELECT * FROM sprints WHERE shortid = $1", &[&shortid])
+			.query_one(
+				"SELECT * FROM sprints WHERE shortid = $1 AND (channel).guild_id = $2",
+				&[&shortid, &guild_id],
+			)
 			.await
 			.into_diagnostic()
 			.and_then(Self::from_row)
@@ -166,6 +313,72 @@ impl Sprint {
 			.wrap_err("db: get current sprints")
 	}
 
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_current_in_guild(app: App, guild_id: i64) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"SELECT * FROM sprints_current WHERE (channel).guild_id = $1 ORDER BY starting_at",
+				&[&guild_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get current sprints in guild")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_current_in_channel(app: App, channel: Channel) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"SELECT * FROM sprints_current WHERE (channel).channel_id = $1 ORDER BY starting_at",
+				&[&channel.channel_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get current sprints in channel")
+	}
+
+	/// The latest sprint in the channel which the member joined and which isn't summaried yet.
+	#[tracing::instrument(skip(app))]
+	pub async fn get_latest_joined_in_channel(
+		app: App,
+		channel: Channel,
+		member: Member,
+	) -> Result<Option<Self>> {
+		app.db
+			.query_opt(
+				"
+				SELECT s.* FROM sprints s
+				JOIN sprint_participants p ON p.sprint_id = s.id
+				WHERE (s.channel).channel_id = $1
+					AND (p.member) = $2::member
+					AND s.cancelled_at IS NULL
+					AND s.status < 'Summaried'
+				ORDER BY s.starting_at DESC
+				LIMIT 1
+				",
+				&[&channel.channel_id, &member],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|row| row.map(Self::from_row).transpose())
+			.wrap_err("db: get latest joined sprint in channel")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_in_chain(app: App, chain_id: Uuid) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"SELECT * FROM sprints WHERE chain_id = $1 AND cancelled_at IS NULL ORDER BY chain_round",
+				&[&chain_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get sprints in chain")
+	}
+
 	#[tracing::instrument(skip(app))]
 	pub async fn get_all_finished_but_not_ended(app: App) -> Result<Vec<Self>> {
 		app.db
@@ -253,6 +466,108 @@ impl Sprint {
 			.map(drop)
 	}
 
+	/// Change the start and duration, returning the updated sprint.
+	///
+	/// This bumps the revision, so timers set up for the sprint as it was will do nothing.
+	#[tracing::instrument(skip(app))]
+	pub async fn reschedule(
+		&self,
+		app: App,
+		starting_at: DateTime<Utc>,
+		duration: Duration,
+	) -> Result<Self> {
+		app.db
+			.query_one(
+				"UPDATE sprints SET starting_at = $2, duration = $3, revision = revision + 1, updated_at = CURRENT_TIMESTAMP WHERE id = $1 RETURNING *",
+				&[
+					&self.id,
+					&starting_at,
+					&Interval::from_duration(duration)
+						.ok_or(miette!("could not convert duration to interval"))?,
+				],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: reschedule sprint")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_chain(&mut self, app: App, chain_id: Uuid, round: i32) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprints SET chain_id = $2, chain_round = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &chain_id, &round],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint chain")?;
+
+		self.chain_id = Some(chain_id);
+		self.chain_round = Some(round);
+		Ok(())
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_measure(&mut self, app: App, measure: SprintMeasure) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprints SET measure = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &measure],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint measure")?;
+
+		self.measure = measure;
+		Ok(())
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_private(&mut self, app: App) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprints SET private = true, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint private")?;
+
+		self.private = true;
+		Ok(())
+	}
+
+	/// Make this a team sprint.
+	#[tracing::instrument(skip(app))]
+	pub async fn set_teams(&mut self, app: App, teams: Vec<String>) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprints SET teams = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &teams],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint teams")?;
+
+		self.teams = Some(teams);
+		Ok(())
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_host(&self, app: App, member: Member) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprints SET host = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &member],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint host")
+			.map(drop)
+	}
+
+	/// Cancel the sprint and any of its pending timers.
 	#[tracing::instrument(skip(app))]
 	pub async fn cancel(&self, app: App) -> Result<()> {
 		app.db
@@ -262,8 +577,9 @@ impl Sprint {
 			)
 			.await
 			.into_diagnostic()
-			.wrap_err("db: cancel sprint")
-			.map(drop)
+			.wrap_err("db: cancel sprint")?;
+
+		app.cancel_timers(&Self::timers_prefix(self.id)).await
 	}
 
 	#[tracing::instrument(skip(app))]
@@ -279,6 +595,34 @@ impl Sprint {
 			.map(drop)
 	}
 
+	/// Join the sprint in a team, or switch teams if already in.
+	#[tracing::instrument(skip(app))]
+	pub async fn join_team(&self, app: App, member: Member, team: &str) -> Result<()> {
+		app.db
+			.query(
+				"INSERT INTO sprint_participants (sprint_id, member, team) VALUES ($1, $2, $3)
+				 ON CONFLICT (sprint_id, member) DO UPDATE SET team = EXCLUDED.team",
+				&[&self.id, &member, &team],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: join sprint team")
+			.map(drop)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_goal(&self, app: App, member: Member, goal: Option<i32>) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprint_participants SET goal = $3 WHERE sprint_id = $1 AND (member) = $2::member",
+				&[&self.id, &member, &goal],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set goal for sprint")
+			.map(drop)
+	}
+
 	#[tracing::instrument(skip(app))]
 	pub async fn leave(&self, app: App, member: Member) -> Result<()> {
 		app.db
@@ -300,27 +644,169 @@ impl Sprint {
 		words: i32,
 		column: &str,
 	) -> Result<()> {
+		// words given by the member replace any that were carried
+		let carried = if column == "words_start" {
+			", words_start_carried = false"
+		} else {
+			""
+		};
 		app.db
 			.query(
-				&format!("UPDATE sprint_participants SET {column} = $3 WHERE sprint_id = $1 AND (member) = $2::member"),
+				&format!("UPDATE sprint_participants SET {column} = $3{carried} WHERE sprint_id = $1 AND (member) = $2::member"),
 				&[&self.id, &member, &words],
 			)
 			.await
 			.into_diagnostic()
-			.wrap_err("db: set words for sprint")
+			.wrap_err("db: set words for sprint")?;
+
+		if column == "words_end" {
+			self.carry_into_next_round(app, member, words).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Correct the starting words carried into the next round of the chain, if there is one.
+	///
+	/// The next round is created when this one ends, before anyone had a chance to give their
+	/// ending words, so what was carried then is from an older sprint.
+	#[tracing::instrument(skip(app))]
+	async fn carry_into_next_round(&self, app: App, member: Member, words_end: i32) -> Result<()> {
+		let (Some(chain_id), Some(round)) = (self.chain_id, self.chain_round) else {
+			return Ok(());
+		};
+		let Some(next) = Self::get_all_in_chain(app.clone(), chain_id)
+			.await?
+			.into_iter()
+			.find(|sprint| sprint.chain_round == Some(round + 1))
+		else {
+			return Ok(());
+		};
+
+		let participant = next
+			.participants(app.clone())
+			.await?
+			.into_iter()
+			.find(|participant| participant.member == member);
+		if let Some(words) =
+			participant.and_then(|participant| participant.start_after_previous_round(words_end))
+		{
+			debug!(%words, ?next.id, "carrying ending words into next round");
+			next.set_carried_words(app, member, words).await?;
+		}
+
+		Ok(())
+	}
+
+	async fn set_carried_words(&self, app: App, member: Member, words: i32) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprint_participants SET words_start = $3, words_start_carried = true WHERE sprint_id = $1 AND (member) = $2::member",
+				&[&self.id, &member, &words],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set carried words for sprint")
 			.map(drop)
 	}
 
+	/// The member's last known total, if they asked for it to carry into their next sprints.
+	///
+	/// That's their ending count from their latest earlier sprint counting the same thing, or
+	/// for word sprints their TrackBear project's total if they haven't sprinted before.
+	#[tracing::instrument(skip(app))]
+	pub async fn carried_words(&self, app: App, member: Member) -> Result<Option<i32>> {
+		let carry = UserPreference::get(app.clone(), member)
+			.await?
+			.is_some_and(|pref| pref.carry_words);
+		if !carry {
+			return Ok(None);
+		}
+
+		let last = app
+			.db
+			.query_opt(
+				"
+				SELECT p.words_end FROM sprint_participants p
+				JOIN sprints s ON s.id = p.sprint_id
+				WHERE (p.member) = $1::member
+					AND s.measure = $2
+					AND s.starting_at < $3
+					AND s.cancelled_at IS NULL
+					AND p.words_end IS NOT NULL
+				ORDER BY s.starting_at DESC
+				LIMIT 1
+				",
+				&[&member, &self.measure, &self.starting_at],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: get last ending words of member")?;
+		if let Some(row) = last {
+			return row.try_get(0).into_diagnostic().map(Some);
+		}
+
+		if self.measure != SprintMeasure::Word {
+			return Ok(None);
+		}
+
+		let Some(project) = Project::get_for_member(app.clone(), member).await? else {
+			return Ok(None);
+		};
+
+		// TrackBear being unreachable shouldn't stop anyone from sprinting
+		Ok(project
+			.word_count(app)
+			.await
+			.log()
+			.ok()
+			.and_then(|count| i32::try_from(count).ok()))
+	}
+
+	/// Set the member's starting words to their carried words, unless they already gave some.
+	#[tracing::instrument(skip(app))]
+	pub async fn carry_words(&self, app: App, member: Member) -> Result<()> {
+		let participant = self.participant(app.clone(), member).await?;
+		if participant.words_start.is_some() {
+			return Ok(());
+		}
+
+		if let Some(words) = self.carried_words(app.clone(), member).await? {
+			debug!(%words, "carrying words into sprint");
+			self.set_carried_words(app, member, words).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Settings of the guild the sprint is in, or the defaults if it's not in one.
+	pub async fn guild_settings(&self, app: App) -> Result<GuildSettings> {
+		match self.channel.and_then(|chan| chan.guild_id) {
+			Some(guild_id) => GuildSettings::get(app, guild_id).await,
+			None => Ok(GuildSettings::default()),
+		}
+	}
+
+	pub fn is_host(&self, member: Member) -> bool {
+		self.host == Some(member)
+	}
+
 	pub fn is_cancelled(&self) -> bool {
 		self.cancelled_at.is_some()
 	}
 
+	/// Prefix of the keys of all the timers of a sprint.
+	pub fn timers_prefix(id: Uuid) -> String {
+		format!("sprint:{id}:")
+	}
+
+	/// Key for one of this sprint's timers.
+	pub fn timer_key(&self, timer: &str) -> String {
+		format!("{}{timer}", Self::timers_prefix(self.id))
+	}
+
 	pub fn duration(&self) -> Duration {
-		Duration::seconds(
-			(self.duration.days as i64 + self.duration.months as i64 * 31)
-				* 24 * 60 * 60 * 1_000_000
-				+ self.duration.microseconds / (1_000_000),
-		)
+		self.duration.to_duration()
 	}
 
 	/// Formatted duration, excluding sign
@@ -334,8 +820,8 @@ impl Sprint {
 		self.starting_at - now
 	}
 
-	pub fn warning_in(&self) -> Duration {
-		self.starting_in().saturating_sub(Duration::seconds(30))
+	pub fn warning_in(&self, before: Duration) -> Duration {
+		self.starting_in().saturating_sub(before)
 	}
 
 	pub fn ending_at(&self) -> DateTime<Utc> {
@@ -365,9 +851,26 @@ impl Sprint {
 			self.starting_at.discord_format('R')
 		};
 
+		let round = match (self.chain_id, self.chain_round) {
+			(Some(chain_id), Some(round)) => {
+				let chain = SprintChain::get(app.clone(), chain_id).await?;
+				format!(" (round {round} of {})", chain.rounds)
+			}
+			_ => String::new(),
+		};
+
+		let counting = match self.measure {
+			SprintMeasure::Word => String::new(),
+			other => format!(", counting {}", other.plural()),
+		};
+
 		Ok(if announce {
+			let host = match self.host {
+				Some(host) => format!(" Hosted by {}.", host.name(app).await?),
+				None => String::new(),
+			};
 			format!(
-				"⏱️  New sprint! `{shortid}` is starting {starting_in_ts} (at {starting_at_ts}), going for {duration}."
+				"⏱️  New sprint! `{shortid}`{round} is starting {starting_in_ts} (at {starting_at_ts}), going for {duration}{counting}.{host}"
 			)
 		} else {
 			let participants = try_join_all(
@@ -379,11 +882,24 @@ impl Sprint {
 			.await?
 			.join(", ");
 			format!(
-				"⏱️ Sprint `{shortid}` starts at {starting_at_ts}, lasts for {duration}, with {participants}."
+				"⏱️ Sprint `{shortid}`{round} starts at {starting_at_ts}, lasts for {duration}{counting}, with {participants}."
 			)
 		})
 	}
 
+	/// The text of the sprint's announcement, as first posted and when it's updated later.
+	#[tracing::instrument(skip(app))]
+	pub async fn announce_text(&self, app: App) -> Result<String> {
+		let content = self.status_text(app.clone(), true).await?;
+		Ok(if self.private {
+			format!("🔒 {content}")
+		} else if let Some(role) = self.guild_settings(app).await?.sprinters_mention() {
+			format!("{content} {role}")
+		} else {
+			content
+		})
+	}
+
 	#[tracing::instrument(skip(app))]
 	pub async fn summary_text(&self, app: App) -> Result<String> {
 		let started_at = self
@@ -396,6 +912,115 @@ impl Sprint {
 
 		let shortid = self.shortid;
 		let duration = self.formatted_duration();
+
+		let summaries = self.summary_lines(app).await?;
+		debug!(?summaries, "sprint summary");
+
+		let summary = if let Some(teams) = &self.teams {
+			let mut totals: Vec<(&String, i32)> = teams
+				.iter()
+				.map(|team| {
+					let words = summaries
+						.iter()
+						.filter(|line| line.team.as_ref() == Some(team))
+						.map(|line| line.words)
+						.sum();
+					(team, words)
+				})
+				.collect();
+			totals.sort_by_key(|(_, words)| -words);
+
+			let mut sections = Vec::with_capacity(totals.len() + 1);
+			for (team, words) in &totals {
+				let members = summaries
+					.iter()
+					.filter(|line| line.team.as_ref() == Some(*team))
+					.join("\n");
+				sections.push(format!(
+					"**Team {team}**: **{words}** {plural}\n{members}",
+					plural = self.measure.plural()
+				));
+			}
+
+			let teamless = summaries
+				.iter()
+				.filter(|line| !line.team.as_ref().is_some_and(|t| teams.contains(t)))
+				.join("\n");
+			if !teamless.is_empty() {
+				sections.push(format!("Without a team:\n{teamless}"));
+			}
+
+			let best = totals.first().map_or(0, |(_, words)| *words);
+			let winners = totals
+				.iter()
+				.filter(|(_, words)| *words == best)
+				.map(|(team, _)| team.as_str())
+				.collect::<Vec<_>>();
+			sections.push(match winners.as_slice() {
+				[winner] => format!("🏆 Team {winner} wins!"),
+				tied => format!("🤝 It's a tie between {}!", tied.join(" and ")),
+			});
+
+			sections.join("\n\n")
+		} else {
+			summaries.iter().join("\n")
+		};
+
+		Ok(format!(
+			"🧮 Sprint `{shortid}`, {duration}, started at {started_at}:\n{summary}"
+		))
+	}
+
+	/// The sprint's summary as a card, ranking participants by how much they wrote.
+	#[tracing::instrument(skip(app))]
+	pub async fn summary_card(&self, app: App) -> Result<SummaryCard> {
+		let mut summaries = self.summary_lines(app).await?;
+		summaries.reverse();
+
+		let values: Vec<f64> = summaries.iter().map(|line| line.words as f64).collect();
+		let rows = summaries
+			.into_iter()
+			.zip(ranks(&values))
+			.map(|(line, rank)| SummaryCardRow {
+				rank,
+				name: match line.team {
+					Some(team) => format!("{} ({team})", line.name),
+					None => line.name,
+				},
+				count: if line.counted {
+					Effect::decorate(
+						line.words.max(0) as u64,
+						line.goal.is_some_and(|goal| line.words >= goal),
+					)
+					.1
+				} else {
+					"—".into()
+				},
+				rate: if line.counted {
+					format!("{:.1} {}", line.wpm, line.measure.rate_unit())
+				} else {
+					"no count".into()
+				},
+			})
+			.collect();
+
+		let round = self
+			.chain_round
+			.map_or(String::new(), |round| format!(", round {round}"));
+		Ok(SummaryCard {
+			title: format!("Sprint {}{round}", self.shortid),
+			subtitle: format!(
+				"{duration} of {plural}, started {started_at}",
+				duration = self.formatted_duration(),
+				plural = self.measure.plural(),
+				started_at = self.starting_at.format("%Y-%m-%d %H:%M UTC"),
+			),
+			rows,
+		})
+	}
+
+	/// Each participant's line in the summary, from least to most written.
+	async fn summary_lines(&self, app: App) -> Result<Vec<SummaryLine>> {
 		let minutes = self.duration().num_minutes();
 
 		let participants = self.participants(app.clone()).await?;
@@ -405,24 +1030,61 @@ impl Sprint {
 			let words = p
 				.words_end
 				.map_or(0, |end| end - p.words_start.unwrap_or(0));
-			let wpm = (words as f64) / (minutes as f64);
-			summaries.push((name, words, wpm));
+			let wpm = self.measure.rate(words, minutes);
+			summaries.push(SummaryLine {
+				name,
+				measure: self.measure,
+				words,
+				wpm,
+				team: p.team,
+				goal: p.goal,
+				counted: p.words_end.is_some(),
+			});
 		}
 
-		summaries.sort_by_key(|(_, w, _)| *w);
-		debug!(?summaries, "sprint summary");
-		let summary = summaries
-			.into_iter()
-			.map(|(name, words, wpm)| {
-				format!(
-					"_{name}_: **{words}** words (**{wpm:.1}** words per minute)",
-					name = name.replace('_', "\\_")
-				)
-			})
-			.join("\n");
+		summaries.sort_by_key(|line| line.words);
+		Ok(summaries)
+	}
+}
 
-		Ok(format!(
-			"🧮 Sprint `{shortid}`, {duration}, started at {started_at}:\n{summary}"
-		))
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn participant(words_start: Option<i32>, words_start_carried: bool) -> Participant {
+		Participant {
+			sprint_id: Uuid::new_v4(),
+			member: Member {
+				guild_id: 1,
+				user_id: 2,
+			},
+			joined_at: Utc::now(),
+			words_start,
+			words_end: None,
+			team: None,
+			goal: None,
+			words_start_carried,
+		}
+	}
+
+	#[test]
+	fn carries_ending_words_across_a_two_round_chain() {
+		// round 1 goes from 1000 to 1500 words
+		let mut first = participant(Some(1000), false);
+		first.words_end = Some(1500);
+
+		// round 2 was created when round 1 ended, so it carried an older total
+		let mut second = participant(Some(800), true);
+		second.words_start = second.start_after_previous_round(first.words_end.unwrap());
+		second.words_end = Some(1700);
+
+		assert_eq!(first.words_written(), Some(500));
+		assert_eq!(second.words_written(), Some(200));
+	}
+
+	#[test]
+	fn keeps_starting_words_given_by_the_member() {
+		let second = participant(Some(1600), false);
+		assert_eq!(second.start_after_previous_round(1500), None);
 	}
 }
@@ -0,0 +1,219 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Duration, Utc};
+use humantime::format_duration;
+use itertools::Itertools;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use pg_interval::Interval;
+use tokio_postgres::Row;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::bot::{
+	utils::time::{ChronoDurationExt, IntervalExt},
+	App,
+};
+
+use super::{member::Member, sprint::Sprint};
+
+/// A series of sprints run back to back, with a break between each round.
+#[derive(Debug, Clone)]
+pub struct SprintChain {
+	pub id: Uuid,
+	pub cancelled_at: Option<DateTime<Utc>>,
+	pub rounds: i32,
+	pub break_duration: Interval,
+}
+
+impl SprintChain {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			id: row.try_get("id").into_diagnostic()?,
+			cancelled_at: row.try_get("cancelled_at").into_diagnostic()?,
+			rounds: row.try_get("rounds").into_diagnostic()?,
+			break_duration: row.try_get("break_duration").into_diagnostic()?,
+		})
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn create(app: App, rounds: i32, break_duration: Duration) -> Result<Self> {
+		app.db
+			.query_one(
+				"INSERT INTO sprint_chains (rounds, break_duration) VALUES ($1, $2) RETURNING *",
+				&[
+					&rounds,
+					&Interval::from_duration(break_duration)
+						.ok_or(miette!("could not convert duration to interval"))?,
+				],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: create sprint chain")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get(app: App, uuid: Uuid) -> Result<Self> {
+		app.db
+			.query_one("SELECT * FROM sprint_chains WHERE id = $1", &[&uuid])
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: get sprint chain")
+	}
+
+	/// Cancel the chain, and any of its sprints that haven't ended yet.
+	#[tracing::instrument(skip(app))]
+	pub async fn cancel(&self, app: App) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprint_chains SET cancelled_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: cancel sprint chain")?;
+
+		let cancelled = app
+			.db
+			.query(
+				"UPDATE sprints SET cancelled_at = CURRENT_TIMESTAMP WHERE chain_id = $1 AND cancelled_at IS NULL AND status < 'Ended' RETURNING id",
+				&[&self.id],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: cancel sprints in chain")?;
+
+		for row in cancelled {
+			let id: Uuid = row.try_get("id").into_diagnostic()?;
+			app.cancel_timers(&Sprint::timers_prefix(id)).await?;
+		}
+
+		Ok(())
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled_at.is_some()
+	}
+
+	pub fn break_duration(&self) -> Duration {
+		self.break_duration.to_duration()
+	}
+
+	/// True if there won't be any more rounds after this sprint.
+	pub fn is_last_round(&self, sprint: &Sprint) -> bool {
+		self.is_cancelled() || sprint.chain_round.unwrap_or(self.rounds) >= self.rounds
+	}
+
+	/// Create the round following this sprint, with the same participants.
+	///
+	/// Returns None if the chain is done or was cancelled.
+	#[tracing::instrument(skip(app))]
+	pub async fn next_round(&self, app: App, sprint: &Sprint) -> Result<Option<Sprint>> {
+		if self.is_last_round(sprint) {
+			debug!("sprint chain is done, not creating another round");
+			return Ok(None);
+		}
+
+		let channel = sprint
+			.channel
+			.ok_or_else(|| miette!("sprint in chain has no channel"))?;
+		let participants = sprint.participants(app.clone()).await?;
+		if participants.is_empty() {
+			debug!("nobody left in the sprint chain, not creating another round");
+			return Ok(None);
+		}
+
+		let mut next = Sprint::create(
+			app.clone(),
+			sprint.ending_at() + self.break_duration(),
+			sprint.duration(),
+			None,
+			channel,
+			sprint.host,
+		)
+		.await?;
+		next.set_chain(app.clone(), self.id, sprint.chain_round.unwrap_or(1) + 1)
+			.await?;
+		next.set_measure(app.clone(), sprint.measure).await?;
+		if sprint.private {
+			next.set_private(app.clone()).await?;
+		}
+		if let Some(teams) = &sprint.teams {
+			next.set_teams(app.clone(), teams.clone()).await?;
+		}
+
+		for participant in &participants {
+			next.join(app.clone(), participant.member).await?;
+		}
+		for participant in &participants {
+			if let Some(team) = &participant.team {
+				next.join_team(app.clone(), participant.member, team)
+					.await?;
+			}
+		}
+		for participant in &participants {
+			next.carry_words(app.clone(), participant.member).await?;
+		}
+
+		Ok(Some(next))
+	}
+
+	/// Totals across all the rounds of the chain, or None if no round was completed.
+	#[tracing::instrument(skip(app))]
+	pub async fn summary_text(&self, app: App) -> Result<Option<String>> {
+		let sprints = Sprint::get_all_in_chain(app.clone(), self.id).await?;
+		if sprints.is_empty() {
+			return Ok(None);
+		}
+
+		// all rounds count the same thing
+		let measure = sprints[0].measure;
+		let total_time = sprints
+			.iter()
+			.fold(Duration::zero(), |total, sprint| total + sprint.duration());
+
+		let mut totals: Vec<(Member, i32, i64)> = Vec::new();
+		for sprint in &sprints {
+			let minutes = sprint.duration().num_minutes();
+			for p in sprint.participants(app.clone()).await? {
+				let words = p.words_written().unwrap_or(0);
+				if let Some(total) = totals
+					.iter_mut()
+					.find(|(member, _, _)| member.user_id == p.member.user_id)
+				{
+					total.1 += words;
+					total.2 += minutes;
+				} else {
+					totals.push((p.member, words, minutes));
+				}
+			}
+		}
+
+		let mut summaries = Vec::with_capacity(totals.len());
+		for (member, words, minutes) in totals {
+			let name = member.name(app.clone()).await?;
+			let wpm = measure.rate(words, minutes);
+			summaries.push((name, words, minutes, wpm));
+		}
+
+		summaries.sort_by_key(|(_, w, _, _)| *w);
+		let summary = summaries
+			.into_iter()
+			.map(|(name, words, minutes, wpm)| {
+				format!(
+					"_{name}_: **{words}** {plural} in {minutes} minutes (**{wpm:.1}** {rate_unit})",
+					name = name.replace('_', "\\_"),
+					plural = measure.plural(),
+					rate_unit = measure.rate_unit(),
+				)
+			})
+			.join("\n");
+
+		Ok(Some(format!(
+			"🔗 Sprint chain, {rounds} rounds for {duration} in total:\n{summary}",
+			rounds = sprints.len(),
+			duration = format_duration(total_time.round_to_seconds()),
+		)))
+	}
+}
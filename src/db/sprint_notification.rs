@@ -0,0 +1,78 @@
+use chrono::Duration;
+use miette::{miette, Context, IntoDiagnostic, Result};
+use pg_interval::Interval;
+use uuid::Uuid;
+
+use crate::bot::App;
+
+use super::member::Member;
+
+/// Members who get a DM when a sprint is announced in their guild.
+#[derive(Debug, Clone, Copy)]
+pub struct SprintNotification;
+
+impl SprintNotification {
+	#[tracing::instrument(skip(app))]
+	pub async fn subscribe(app: App, member: Member) -> Result<()> {
+		app.db
+			.execute(
+				"INSERT INTO sprint_notifications (member) VALUES ($1) ON CONFLICT DO NOTHING",
+				&[&member],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: subscribe to sprint notifications")
+			.map(drop)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn unsubscribe(app: App, member: Member) -> Result<()> {
+		app.db
+			.execute(
+				"DELETE FROM sprint_notifications WHERE (member) = $1::member",
+				&[&member],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: unsubscribe from sprint notifications")
+			.map(drop)
+	}
+
+	/// Members of the guild to notify about a sprint, marking them as notified.
+	///
+	/// Members who were notified less than `cooldown` ago are skipped, so a busy guild doesn't
+	/// flood their DMs; so are members who are already in the sprint.
+	#[tracing::instrument(skip(app))]
+	pub async fn claim_for_sprint(
+		app: App,
+		guild_id: i64,
+		sprint_id: Uuid,
+		cooldown: Duration,
+	) -> Result<Vec<Member>> {
+		let cooldown = Interval::from_duration(cooldown)
+			.ok_or_else(|| miette!("could not convert cooldown to interval"))?;
+		app.db
+			.query(
+				"
+				UPDATE sprint_notifications n
+				SET last_notified_at = current_timestamp
+				WHERE (n.member).guild_id = $1
+					AND (n.last_notified_at IS NULL OR n.last_notified_at < current_timestamp - $3::interval)
+					AND NOT EXISTS (
+						SELECT 1 FROM sprint_participants p
+						WHERE p.sprint_id = $2 AND p.member = n.member
+					)
+				RETURNING n.member
+				",
+				&[&guild_id, &sprint_id, &cooldown],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| {
+				rows.into_iter()
+					.map(|row| row.try_get("member").into_diagnostic())
+					.collect()
+			})
+			.wrap_err("db: claim sprint notifications")
+	}
+}
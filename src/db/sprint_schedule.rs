@@ -0,0 +1,195 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Duration, Utc};
+use humantime::{format_duration, FormattedDuration};
+use miette::{miette, Context, IntoDiagnostic, Result};
+use pg_interval::Interval;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::bot::{
+	utils::{
+		recurrence::Recurrence,
+		time::{ChronoDurationExt, IntervalExt},
+	},
+	App,
+};
+
+use super::{channel::Channel, member::Member};
+
+#[derive(Debug, Clone)]
+pub struct SprintSchedule {
+	pub id: Uuid,
+	pub shortid: i32,
+	pub paused_at: Option<DateTime<Utc>>,
+	pub member: Member,
+	pub channel: Channel,
+	pub days: String,
+	pub times: String,
+	pub duration: Interval,
+	pub last_run_at: Option<DateTime<Utc>>,
+	pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl SprintSchedule {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			id: row.try_get("id").into_diagnostic()?,
+			shortid: row.try_get("shortid").into_diagnostic()?,
+			paused_at: row.try_get("paused_at").into_diagnostic()?,
+			member: row.try_get("member").into_diagnostic()?,
+			channel: row.try_get("channel").into_diagnostic()?,
+			days: row.try_get("days").into_diagnostic()?,
+			times: row.try_get("times").into_diagnostic()?,
+			duration: row.try_get("duration").into_diagnostic()?,
+			last_run_at: row.try_get("last_run_at").into_diagnostic()?,
+			next_run_at: row.try_get("next_run_at").into_diagnostic()?,
+		})
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn create(
+		app: App,
+		member: Member,
+		channel: Channel,
+		recurrence: (&str, &str),
+		duration: Duration,
+	) -> Result<Self> {
+		let (days, times) = recurrence;
+		app.db
+			.query_one(
+				"INSERT INTO sprint_schedules (member, channel, days, times, duration) VALUES ($1, $2, $3, $4, $5) RETURNING *",
+				&[
+					&member,
+					&channel,
+					&days,
+					&times,
+					&Interval::from_duration(duration)
+						.ok_or(miette!("could not convert duration to interval"))?,
+				],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: create sprint schedule")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get(app: App, uuid: Uuid) -> Result<Option<Self>> {
+		app.db
+			.query_opt("SELECT * FROM sprint_schedules WHERE id = $1", &[&uuid])
+			.await
+			.into_diagnostic()
+			.and_then(|row| row.map(Self::from_row).transpose())
+			.wrap_err("db: get sprint schedule")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_from_shortid(app: App, guild_id: i64, shortid: i32) -> Result<Self> {
+		app.db
+			.query_one(
+				"SELECT * FROM sprint_schedules WHERE shortid = $1 AND (channel).guild_id = $2",
+				&[&shortid, &guild_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: get sprint schedule from shortid")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_in_guild(app: App, guild_id: i64) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"SELECT * FROM sprint_schedules WHERE (channel).guild_id = $1 ORDER BY shortid",
+				&[&guild_id],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get sprint schedules in guild")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_active(app: App) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"SELECT * FROM sprint_schedules WHERE paused_at IS NULL",
+				&[],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get active sprint schedules")
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_paused(&self, app: App, paused: bool) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprint_schedules SET paused_at = CASE WHEN $2 THEN CURRENT_TIMESTAMP END, next_run_at = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &paused],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint schedule paused")
+			.map(drop)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn set_next_run(&self, app: App, next_run_at: DateTime<Utc>) -> Result<()> {
+		app.db
+			.query(
+				"UPDATE sprint_schedules SET next_run_at = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+				&[&self.id, &next_run_at],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: set sprint schedule next run")
+			.map(drop)
+	}
+
+	/// Mark the run at the given time as done, if it's still the one that's due.
+	///
+	/// Returns false if the schedule was paused, deleted, or rescheduled since the run was set up,
+	/// or if another timer already claimed it.
+	#[tracing::instrument(skip(app))]
+	pub async fn claim_run(&self, app: App, run_at: DateTime<Utc>) -> Result<bool> {
+		app.db
+			.execute(
+				"UPDATE sprint_schedules SET last_run_at = $2, next_run_at = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND next_run_at = $2 AND paused_at IS NULL",
+				&[&self.id, &run_at],
+			)
+			.await
+			.into_diagnostic()
+			.wrap_err("db: claim sprint schedule run")
+			.map(|updated| updated > 0)
+	}
+
+	#[tracing::instrument(skip(app))]
+	pub async fn delete(&self, app: App) -> Result<()> {
+		app.db
+			.query("DELETE FROM sprint_schedules WHERE id = $1", &[&self.id])
+			.await
+			.into_diagnostic()
+			.wrap_err("db: delete sprint schedule")
+			.map(drop)
+	}
+
+	pub fn is_paused(&self) -> bool {
+		self.paused_at.is_some()
+	}
+
+	pub fn recurrence(&self) -> Result<Recurrence> {
+		Recurrence::parse(&self.days, &self.times)
+	}
+
+	pub fn duration(&self) -> Duration {
+		self.duration.to_duration()
+	}
+
+	/// Formatted duration, excluding sign
+	pub fn formatted_duration(&self) -> FormattedDuration {
+		format_duration(self.duration().round_to_seconds())
+	}
+}
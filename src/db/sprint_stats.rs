@@ -0,0 +1,210 @@
+use chrono::{DateTime, Duration, Utc};
+use miette::{Context, IntoDiagnostic, Result};
+use pg_interval::Interval;
+use tokio_postgres::Row;
+
+use crate::bot::{utils::time::IntervalExt, App};
+
+use super::member::Member;
+
+/// A member's totals over the sprints they finished.
+///
+/// Only word sprints which ended and for which the member gave both starting and ending words
+/// count; sprints measured in pages, scenes, etc aren't comparable.
+#[derive(Debug, Clone)]
+pub struct SprintStats {
+	pub sprints: i64,
+	pub words: i64,
+	pub minutes: f64,
+	pub best_wpm: Option<f64>,
+	pub longest: Option<Interval>,
+	pub goals_set: i64,
+	pub goals_met: i64,
+}
+
+/// One finished sprint of a member.
+#[derive(Debug, Clone)]
+pub struct SprintHistoryEntry {
+	pub shortid: i32,
+	pub starting_at: DateTime<Utc>,
+	pub duration: Interval,
+	pub words: i32,
+}
+
+impl SprintStats {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			sprints: row.try_get("sprints").into_diagnostic()?,
+			words: row.try_get("words").into_diagnostic()?,
+			minutes: row.try_get("minutes").into_diagnostic()?,
+			best_wpm: row.try_get("best_wpm").into_diagnostic()?,
+			longest: row.try_get("longest").into_diagnostic()?,
+			goals_set: row.try_get("goals_set").into_diagnostic()?,
+			goals_met: row.try_get("goals_met").into_diagnostic()?,
+		})
+	}
+
+	/// Stats for the member, over sprints which started after `since` (or all of them).
+	#[tracing::instrument(skip(app))]
+	pub async fn get(app: App, member: Member, since: Option<DateTime<Utc>>) -> Result<Self> {
+		app.db
+			.query_one(
+				"
+				SELECT
+					count(*) AS sprints,
+					coalesce(sum(p.words_end - p.words_start), 0)::bigint AS words,
+					coalesce(sum(extract(epoch FROM s.duration) / 60), 0)::float8 AS minutes,
+					max((p.words_end - p.words_start) / nullif(extract(epoch FROM s.duration) / 60, 0))::float8 AS best_wpm,
+					max(s.duration) AS longest,
+					count(p.goal) AS goals_set,
+					count(*) FILTER (WHERE p.words_end - p.words_start >= p.goal) AS goals_met
+				FROM sprint_participants p
+				JOIN sprints s ON s.id = p.sprint_id
+				WHERE (p.member) = $1::member
+					AND s.cancelled_at IS NULL
+					AND s.status >= 'Ended'
+					AND s.measure = 'Word'
+					AND p.words_start IS NOT NULL
+					AND p.words_end IS NOT NULL
+					AND ($2::timestamptz IS NULL OR s.starting_at >= $2)
+				",
+				&[&member, &since],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(Self::from_row)
+			.wrap_err("db: get sprint stats")
+	}
+
+	/// Average words per minute across all sprints, or None if there weren't any.
+	pub fn average_wpm(&self) -> Option<f64> {
+		(self.minutes > 0.0).then(|| self.words as f64 / self.minutes)
+	}
+
+	/// Proportion of goals which were met, or None if no goals were set.
+	pub fn goal_hit_rate(&self) -> Option<f64> {
+		(self.goals_set > 0).then(|| self.goals_met as f64 / self.goals_set as f64)
+	}
+
+	pub fn longest(&self) -> Option<Duration> {
+		self.longest.as_ref().map(|longest| longest.to_duration())
+	}
+}
+
+impl SprintHistoryEntry {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			shortid: row.try_get("shortid").into_diagnostic()?,
+			starting_at: row.try_get("starting_at").into_diagnostic()?,
+			duration: row.try_get("duration").into_diagnostic()?,
+			words: row.try_get("words").into_diagnostic()?,
+		})
+	}
+
+	/// The member's most recent finished sprints, latest first.
+	#[tracing::instrument(skip(app))]
+	pub async fn get_recent(
+		app: App,
+		member: Member,
+		since: Option<DateTime<Utc>>,
+		limit: i64,
+	) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"
+				SELECT
+					s.shortid,
+					s.starting_at,
+					s.duration,
+					p.words_end - p.words_start AS words
+				FROM sprint_participants p
+				JOIN sprints s ON s.id = p.sprint_id
+				WHERE (p.member) = $1::member
+					AND s.cancelled_at IS NULL
+					AND s.status >= 'Ended'
+					AND s.measure = 'Word'
+					AND p.words_start IS NOT NULL
+					AND p.words_end IS NOT NULL
+					AND ($2::timestamptz IS NULL OR s.starting_at >= $2)
+				ORDER BY s.starting_at DESC
+				LIMIT $3
+				",
+				&[&member, &since, &limit],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get recent sprints of member")
+	}
+
+	pub fn duration(&self) -> Duration {
+		self.duration.to_duration()
+	}
+
+	pub fn wpm(&self) -> f64 {
+		self.words as f64 / self.duration().num_minutes().max(1) as f64
+	}
+}
+
+/// A member's totals for a guild leaderboard.
+#[derive(Debug, Clone)]
+pub struct LeaderboardEntry {
+	pub member: Member,
+	pub sprints: i64,
+	pub words: i64,
+	pub minutes: f64,
+	pub best_wpm: Option<f64>,
+}
+
+impl LeaderboardEntry {
+	fn from_row(row: Row) -> Result<Self> {
+		Ok(Self {
+			member: row.try_get("member").into_diagnostic()?,
+			sprints: row.try_get("sprints").into_diagnostic()?,
+			words: row.try_get("words").into_diagnostic()?,
+			minutes: row.try_get("minutes").into_diagnostic()?,
+			best_wpm: row.try_get("best_wpm").into_diagnostic()?,
+		})
+	}
+
+	/// Totals of every member of the guild over sprints which started in the given range.
+	///
+	/// Members who opted out of leaderboards are left out.
+	#[tracing::instrument(skip(app))]
+	pub async fn get_all_in_guild(
+		app: App,
+		guild_id: i64,
+		from: DateTime<Utc>,
+		until: DateTime<Utc>,
+	) -> Result<Vec<Self>> {
+		app.db
+			.query(
+				"
+				SELECT
+					p.member,
+					count(*) AS sprints,
+					sum(p.words_end - p.words_start)::bigint AS words,
+					sum(extract(epoch FROM s.duration) / 60)::float8 AS minutes,
+					max((p.words_end - p.words_start) / nullif(extract(epoch FROM s.duration) / 60, 0))::float8 AS best_wpm
+				FROM sprint_participants p
+				JOIN sprints s ON s.id = p.sprint_id
+				LEFT JOIN user_preferences up ON up.member = p.member
+				WHERE (p.member).guild_id = $1
+					AND s.cancelled_at IS NULL
+					AND s.status >= 'Ended'
+					AND s.measure = 'Word'
+					AND p.words_start IS NOT NULL
+					AND p.words_end IS NOT NULL
+					AND s.starting_at >= $2
+					AND s.starting_at < $3
+					AND NOT coalesce(up.leaderboard_opt_out, false)
+				GROUP BY p.member
+				",
+				&[&guild_id, &from, &until],
+			)
+			.await
+			.into_diagnostic()
+			.and_then(|rows| rows.into_iter().map(Self::from_row).collect())
+			.wrap_err("db: get sprint leaderboard")
+	}
+}
@@ -136,20 +136,29 @@ impl TrackbearLogin {
 			.map(drop)
 	}
 
-	#[tracing::instrument]
-	pub async fn client(&self) -> Result<TrackbearClient> {
-		let client = TrackbearClient::new(self.api_key.clone())?;
+	#[tracing::instrument(skip(app))]
+	pub async fn client(&self, app: App) -> Result<TrackbearClient> {
+		let client = app.trackbear.client(self.api_key.clone());
 		client.validate().await?;
 		Ok(client)
 	}
 
+	/// Get a TrackBear client without checking the API key, or None if it's empty.
+	///
+	/// Checking the key needs TrackBear to be up, so this is for requests which are retried
+	/// later if it's down.
+	pub fn client_unchecked(&self, app: App) -> Option<TrackbearClient> {
+		(!self.api_key.as_sensitive_str().is_empty())
+			.then(|| app.trackbear.client(self.api_key.clone()))
+	}
+
 	/// Get a validated TrackBear client for a member, returning None if:
 	/// - The member has no login row
 	/// - The API key is empty (migrated from old system but not logged in yet)
 	/// - The API key is invalid
 	#[tracing::instrument(skip(app))]
 	pub async fn client_for_member(app: App, member: Member) -> Result<Option<TrackbearClient>> {
-		let Some(login) = Self::get_for_member(app, member).await? else {
+		let Some(login) = Self::get_for_member(app.clone(), member).await? else {
 			return Ok(None);
 		};
 
@@ -159,7 +168,7 @@ impl TrackbearLogin {
 		}
 
 		// Try to get a client, but return None if it fails (e.g., invalid key)
-		match login.client().await {
+		match login.client(app).await {
 			Ok(client) => Ok(Some(client)),
 			Err(_) => Ok(None),
 		}
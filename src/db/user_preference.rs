@@ -9,6 +9,11 @@ use super::member::Member;
 pub struct UserPreference {
 	pub member: Member,
 	pub timezone: String,
+	pub leaderboard_opt_out: bool,
+	pub carry_words: bool,
+	pub sprint_reminders: bool,
+	/// How long before sprints start to remind this member, in minutes.
+	pub reminder_minutes: i32,
 }
 
 impl UserPreference {
@@ -19,7 +24,7 @@ impl UserPreference {
 			.query_opt(
 				"INSERT INTO user_preferences (member) VALUES ($1)
 				 ON CONFLICT (member) DO UPDATE SET member = EXCLUDED.member
-				 RETURNING member, timezone",
+				 RETURNING member, timezone, leaderboard_opt_out, carry_words, sprint_reminders, reminder_minutes",
 				&[&member],
 			)
 			.await
@@ -29,12 +34,20 @@ impl UserPreference {
 			Ok(Self {
 				member: row.get(0),
 				timezone: row.get(1),
+				leaderboard_opt_out: row.get(2),
+				carry_words: row.get(3),
+				sprint_reminders: row.get(4),
+				reminder_minutes: row.get(5),
 			})
 		} else {
 			// Fallback to default if somehow no row was returned
 			Ok(Self {
 				member,
 				timezone: "Pacific/Auckland".to_string(),
+				leaderboard_opt_out: false,
+				carry_words: false,
+				sprint_reminders: true,
+				reminder_minutes: 10,
 			})
 		}
 	}
@@ -44,7 +57,7 @@ impl UserPreference {
 		let row = app
 			.db
 			.query_opt(
-				"SELECT member, timezone FROM user_preferences WHERE member = $1",
+				"SELECT member, timezone, leaderboard_opt_out, carry_words, sprint_reminders, reminder_minutes FROM user_preferences WHERE member = $1",
 				&[&member],
 			)
 			.await
@@ -53,6 +66,10 @@ impl UserPreference {
 		Ok(row.map(|row| Self {
 			member: row.get(0),
 			timezone: row.get(1),
+			leaderboard_opt_out: row.get(2),
+			carry_words: row.get(3),
+			sprint_reminders: row.get(4),
+			reminder_minutes: row.get(5),
 		}))
 	}
 
@@ -72,6 +89,55 @@ impl UserPreference {
 		Ok(self)
 	}
 
+	/// Set whether this member is left out of sprint leaderboards
+	pub async fn set_leaderboard_opt_out(mut self, app: App, opt_out: bool) -> Result<Self> {
+		debug!(?self.member, %opt_out, "updating user leaderboard opt-out");
+
+		app.db
+			.execute(
+				"UPDATE user_preferences SET leaderboard_opt_out = $1 WHERE member = $2",
+				&[&opt_out, &self.member],
+			)
+			.await
+			.into_diagnostic()?;
+
+		self.leaderboard_opt_out = opt_out;
+		Ok(self)
+	}
+
+	/// Set whether this member's last known total becomes their next sprints' starting words
+	pub async fn set_carry_words(mut self, app: App, carry_words: bool) -> Result<Self> {
+		debug!(?self.member, %carry_words, "updating user carry words");
+
+		app.db
+			.execute(
+				"UPDATE user_preferences SET carry_words = $1 WHERE member = $2",
+				&[&carry_words, &self.member],
+			)
+			.await
+			.into_diagnostic()?;
+
+		self.carry_words = carry_words;
+		Ok(self)
+	}
+
+	/// Set whether this member gets DMs about sprints they joined, and how early
+	pub async fn set_reminders(mut self, app: App, enabled: bool, minutes: i32) -> Result<Self> {
+		debug!(?self.member, %enabled, %minutes, "updating user sprint reminders");
+
+		app.db
+			.execute(
+				"UPDATE user_preferences SET sprint_reminders = $1, reminder_minutes = $2 WHERE member = $3",
+				&[&enabled, &minutes, &self.member],
+			)
+			.await
+			.into_diagnostic()?;
+
+		self.sprint_reminders = enabled;
+		self.reminder_minutes = minutes;
+		Ok(self)
+	}
+
 	/// Get the timezone as a chrono_tz::Tz
 	pub fn timezone_tz(&self) -> Result<chrono_tz::Tz> {
 		self.timezone
@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
-use miette::Result;
-use tokio::runtime::Builder;
+use miette::{IntoDiagnostic, Result};
+use tokio::{io::AsyncWriteExt, runtime::Builder};
 
 pub(crate) mod bot;
 pub(crate) mod config;
@@ -45,6 +45,36 @@ async fn entry() -> Result<()> {
 		Command::Start => {
 			bot::start(config).await?;
 		}
+		Command::Export {
+			guild,
+			user,
+			since,
+			until,
+			format,
+			output,
+		} => {
+			let filter = db::export::ExportFilter {
+				guild_id: guild,
+				user_id: user,
+				include_private: true,
+				..Default::default()
+			}
+			.with_dates(since.as_deref(), until.as_deref())?;
+
+			let (client, db_task) = config.db.connect().await?;
+			let querying = tokio::spawn(db_task);
+			let rows = db::export::ExportRow::query(&client, &filter).await?;
+			querying.abort();
+
+			let file = db::export::encode(&rows, format)?;
+			match output {
+				Some(path) => tokio::fs::write(path, file).await.into_diagnostic()?,
+				None => tokio::io::stdout()
+					.write_all(&file)
+					.await
+					.into_diagnostic()?,
+			}
+		}
 	}
 
 	Ok(())
@@ -73,4 +103,31 @@ enum Command {
 
 	/// Start bot
 	Start,
+
+	/// Export sprints and their participants' words
+	Export {
+		/// Only sprints in this guild
+		#[arg(long, value_name = "ID")]
+		guild: Option<i64>,
+
+		/// Only this user's participation
+		#[arg(long, value_name = "ID")]
+		user: Option<i64>,
+
+		/// Only sprints on or after this date (YYYY-MM-DD, UTC)
+		#[arg(long, value_name = "DATE")]
+		since: Option<String>,
+
+		/// Only sprints on or before this date (YYYY-MM-DD, UTC)
+		#[arg(long, value_name = "DATE")]
+		until: Option<String>,
+
+		/// File format: csv or json
+		#[arg(long, default_value = "csv")]
+		format: db::export::ExportFormat,
+
+		/// Write to this file instead of stdout
+		#[arg(short, long, value_name = "FILE")]
+		output: Option<PathBuf>,
+	},
 }
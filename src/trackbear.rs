@@ -1,5 +1,7 @@
 pub mod client;
 pub mod project;
+pub mod shared;
 
 pub use client::TrackbearClient;
 pub use project::Project;
+pub use shared::Trackbear;
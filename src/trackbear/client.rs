@@ -1,27 +1,105 @@
-use miette::{miette, Context, IntoDiagnostic, Result};
-use reqwest::{header, Client, StatusCode};
+use std::{fmt, str::FromStr, time::Duration};
+
+use miette::{miette, Context, Diagnostic, IntoDiagnostic, Report, Result};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use secret_vault_value::SecretValue;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use tracing::{debug, warn};
 
-const API_BASE_URL: &str = "https://trackbear.app/api/v1";
+use super::shared::{header_number, Trackbear, MAX_RATE_LIMIT_WAIT};
+
+/// Wait before the first retry of a failed request, doubled for each retry after that.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Stop doubling the backoff after this many retries, which is about 30 seconds apart.
+const MAX_BACKOFF_DOUBLINGS: u32 = 6;
+
+fn backoff(attempt: u32) -> Duration {
+	RETRY_BACKOFF * 2_u32.pow(attempt.min(MAX_BACKOFF_DOUBLINGS))
+}
+
+/// A failure to get an answer out of TrackBear.
+#[derive(Debug)]
+pub enum ApiError {
+	/// TrackBear couldn't be reached or didn't answer in time.
+	Unreachable(reqwest::Error),
+	/// TrackBear answered with an error status.
+	Status { status: StatusCode, body: String },
+	/// TrackBear asked us to slow down for longer than we're willing to wait.
+	RateLimited(Duration),
+}
+
+impl ApiError {
+	/// Whether the request didn't go through, but might if it's tried again later.
+	///
+	/// A request which timed out may or may not have gone through, so it doesn't count: trying
+	/// it again could make a tally twice.
+	pub fn is_transient(&self) -> bool {
+		match self {
+			Self::Unreachable(err) => err.is_connect(),
+			Self::RateLimited(_) => true,
+			Self::Status { status, .. } => {
+				status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+			}
+		}
+	}
+
+	/// Whether TrackBear didn't answer in time, so the request may have gone through anyway.
+	pub fn is_timeout(&self) -> bool {
+		matches!(self, Self::Unreachable(err) if err.is_timeout())
+	}
+}
 
-fn user_agent() -> String {
-	format!(
-		"{}/{} ({}) by {}",
-		env!("CARGO_PKG_NAME"),
-		env!("CARGO_PKG_VERSION"),
-		env!("CARGO_PKG_REPOSITORY"),
-		env!("CARGO_PKG_AUTHORS")
-	)
+impl fmt::Display for ApiError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Unreachable(err) if err.is_timeout() => write!(
+				f,
+				"TrackBear API didn't answer in time, check whether it went through before trying again"
+			),
+			Self::Unreachable(_) => write!(f, "failed to connect to TrackBear API"),
+			Self::Status { status, body } => {
+				write!(f, "TrackBear API returned error (status {status}): {body}")
+			}
+			Self::RateLimited(wait) => write!(
+				f,
+				"TrackBear asked us to slow down, try again in {} seconds",
+				wait.as_secs()
+			),
+		}
+	}
 }
 
+impl std::error::Error for ApiError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Unreachable(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl Diagnostic for ApiError {}
+
+/// Whether a request which failed with this error is safe to try again later.
+pub fn is_transient(err: &Report) -> bool {
+	err.downcast_ref::<ApiError>()
+		.is_some_and(ApiError::is_transient)
+}
+
+/// Whether a request which failed with this error may have gone through anyway.
+pub fn is_timeout(err: &Report) -> bool {
+	err.downcast_ref::<ApiError>()
+		.is_some_and(ApiError::is_timeout)
+}
+
+/// A client for one TrackBear login, sharing connections and caches with all the others.
 #[derive(Debug, Clone)]
 pub struct TrackbearClient {
-	client: Client,
-	#[expect(dead_code, reason = "unused")]
+	trackbear: Trackbear,
 	api_key: SecretValue,
+	cache_key: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,7 +110,7 @@ struct ApiResponse<T> {
 }
 
 /// Helper to parse API response, logging bytes on failure
-async fn parse_api_response<T: for<'de> Deserialize<'de>>(
+async fn parse_api_response<T: DeserializeOwned>(
 	response: reqwest::Response,
 	context: &str,
 ) -> Result<T> {
@@ -90,7 +168,7 @@ pub struct Project {
 	pub totals: Option<Balance>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GoalThreshold {
 	pub measure: Measure,
@@ -108,10 +186,95 @@ pub enum Measure {
 	Line,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl Measure {
+	/// Plural name of the unit, like "words" or "minutes".
+	pub fn plural(&self) -> &'static str {
+		match self {
+			Self::Word => "words",
+			Self::Time => "minutes",
+			Self::Page => "pages",
+			Self::Chapter => "chapters",
+			Self::Scene => "scenes",
+			Self::Line => "lines",
+		}
+	}
+}
+
+impl FromStr for Measure {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"word" => Ok(Self::Word),
+			"time" => Ok(Self::Time),
+			"page" => Ok(Self::Page),
+			"chapter" => Ok(Self::Chapter),
+			"scene" => Ok(Self::Scene),
+			"line" => Ok(Self::Line),
+			other => Err(format!("unknown measure: {other}")),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GoalType {
+	/// Reach a total by the end date.
+	Target,
+	/// Write some amount every day, week, etc.
+	Habit,
+	#[serde(other)]
+	Other,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CadenceUnit {
+	Day,
+	Week,
+	Month,
+	Year,
+}
+
+impl CadenceUnit {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Day => "day",
+			Self::Week => "week",
+			Self::Month => "month",
+			Self::Year => "year",
+		}
+	}
+}
+
+impl FromStr for CadenceUnit {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"day" => Ok(Self::Day),
+			"week" => Ok(Self::Week),
+			"month" => Ok(Self::Month),
+			"year" => Ok(Self::Year),
+			other => Err(format!("unknown cadence: {other}")),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalCadence {
+	pub unit: CadenceUnit,
+	pub period: i64,
+}
+
+/// Target goals always have a threshold; habit goals may only count sessions, without one.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GoalParameters {
-	pub threshold: GoalThreshold,
+	pub threshold: Option<GoalThreshold>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cadence: Option<GoalCadence>,
 }
 
 #[expect(dead_code, reason = "unused fields")]
@@ -127,7 +290,7 @@ pub struct Goal {
 	pub title: String,
 	pub description: Option<String>,
 	#[serde(rename = "type")]
-	pub goal_type: String,
+	pub goal_type: GoalType,
 	pub parameters: GoalParameters,
 	pub start_date: Option<String>,
 	pub end_date: Option<String>,
@@ -167,11 +330,13 @@ pub struct Tally {
 	pub count: i64,
 	pub note: Option<String>,
 	pub work_id: i64,
-	pub work: Project,
+	#[serde(default)]
+	pub work: Option<Project>,
+	#[serde(default)]
 	pub tags: Vec<Tag>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTallyRequest {
 	pub date: String,
@@ -183,75 +348,143 @@ pub struct CreateTallyRequest {
 	pub tags: Vec<String>,
 }
 
-impl TrackbearClient {
-	/// Create a new TrackBear client with an API key
-	pub fn new(api_key: SecretValue) -> Result<Self> {
-		let mut headers = header::HeaderMap::new();
-
-		// Add User-Agent header
-		let user_agent_value = user_agent();
-		headers.insert(
-			header::USER_AGENT,
-			header::HeaderValue::from_str(&user_agent_value)
-				.into_diagnostic()
-				.wrap_err("invalid user agent format")?,
-		);
+/// Changes to a tally; fields left as `None` are left as they are.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTallyRequest {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub date: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub count: Option<i64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
 
-		// Add Authorization header with Bearer token
-		let auth_value = format!("Bearer {}", api_key.as_sensitive_str());
-		headers.insert(
-			header::AUTHORIZATION,
-			header::HeaderValue::from_str(&auth_value)
-				.into_diagnostic()
-				.wrap_err("invalid API key format")?,
-		);
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGoalRequest {
+	pub title: String,
+	pub description: String,
+	#[serde(rename = "type")]
+	pub goal_type: GoalType,
+	pub parameters: GoalParameters,
+	pub start_date: Option<String>,
+	pub end_date: Option<String>,
+	pub work_ids: Vec<i64>,
+	pub tag_ids: Vec<i64>,
+	pub starred: bool,
+	pub display_on_profile: bool,
+}
 
-		let client = Client::builder()
-			.default_headers(headers)
-			.build()
-			.into_diagnostic()
-			.wrap_err("failed to build HTTP client")?;
+/// Changes to a goal; fields left as `None` are left as they are.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGoalRequest {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub parameters: Option<GoalParameters>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub start_date: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub end_date: Option<String>,
+}
 
-		Ok(Self { client, api_key })
+impl TrackbearClient {
+	pub(super) fn new(trackbear: Trackbear, api_key: SecretValue, cache_key: u64) -> Self {
+		Self {
+			trackbear,
+			api_key,
+			cache_key,
+		}
 	}
 
-	/// Test API connectivity without authentication
-	pub async fn ping_service() -> Result<bool> {
-		let client = Client::builder()
-			.user_agent(user_agent())
-			.build()
-			.into_diagnostic()
-			.wrap_err("failed to build HTTP client")?;
-
-		let url = format!("{}/ping", API_BASE_URL);
-		debug!("pinging TrackBear service at {}", url);
-
-		match client.get(&url).send().await {
-			Ok(response) => {
-				let status = response.status();
-				debug!("service ping response: {}", status);
-				// Just check status code, no JSON response body
-				Ok(status.is_success())
-			}
-			Err(err) => {
-				warn!("service ping failed: {}", err);
-				Ok(false)
+	/// Send a request, retrying failures with backoff, and waiting out rate limits.
+	///
+	/// Timeouts and server errors are only retried for requests which are safe to repeat: a
+	/// POST which timed out may well have gone through, and sending it again would duplicate
+	/// it. Those are only retried if TrackBear couldn't be reached at all.
+	///
+	/// Once out of retries, the last response is returned as-is for the caller to report.
+	async fn send(
+		&self,
+		context: &str,
+		build: impl Fn(&Client) -> RequestBuilder,
+	) -> Result<Response> {
+		let shared = self.trackbear.shared();
+		let mut attempt = 0;
+
+		loop {
+			self.trackbear.wait_for_rate_limit().await?;
+
+			let request = build(&shared.http)
+				.bearer_auth(self.api_key.as_sensitive_str())
+				.build()
+				.map_err(ApiError::Unreachable)?;
+			let repeatable = request.method() != Method::POST;
+			let result = shared.http.execute(request).await;
+
+			let retry_in = match &result {
+				Ok(response) => {
+					self.trackbear.observe_rate_limit(response.headers());
+					match response.status() {
+						StatusCode::TOO_MANY_REQUESTS => {
+							let wait = header_number(response.headers(), "retry-after")
+								.or_else(|| header_number(response.headers(), "ratelimit-reset"))
+								.map(Duration::from_secs)
+								.unwrap_or_else(|| backoff(attempt));
+							self.trackbear.rate_limited_for(wait);
+							(wait <= MAX_RATE_LIMIT_WAIT).then_some(Duration::ZERO)
+						}
+						status if status.is_server_error() && repeatable => Some(backoff(attempt)),
+						_ => None,
+					}
+				}
+				Err(err) if err.is_connect() || (err.is_timeout() && repeatable) => {
+					Some(backoff(attempt))
+				}
+				Err(_) => None,
+			};
+
+			match retry_in {
+				Some(wait) if attempt < shared.retries => {
+					warn!(%context, %attempt, ?wait, "retrying TrackBear request");
+					tokio::time::sleep(wait).await;
+					attempt += 1;
+				}
+				_ => return result.map_err(|err| ApiError::Unreachable(err).into()),
 			}
 		}
 	}
 
+	/// Send a request and parse the data out of a successful response.
+	async fn call<T: DeserializeOwned>(
+		&self,
+		context: &str,
+		build: impl Fn(&Client) -> RequestBuilder,
+	) -> Result<T> {
+		let response = self.send(context, build).await?;
+
+		let status = response.status();
+		debug!("{} response: {}", context, status);
+
+		if !status.is_success() {
+			let body = response
+				.text()
+				.await
+				.unwrap_or_else(|_| "unknown error".to_string());
+			return Err(ApiError::Status { status, body }.into());
+		}
+
+		parse_api_response(response, context).await
+	}
+
 	/// Test API key validity by pinging with authentication
 	pub async fn ping_with_token(&self) -> Result<()> {
-		let url = format!("{}/ping/api-token", API_BASE_URL);
+		let url = self.trackbear.url("ping/api-token");
 		debug!("pinging TrackBear API with token");
 
-		let response = self
-			.client
-			.get(&url)
-			.send()
-			.await
-			.into_diagnostic()
-			.wrap_err("failed to connect to TrackBear API")?;
+		let response = self.send("api token ping", |http| http.get(&url)).await?;
 
 		let status = response.status();
 		debug!("API token ping response: {}", status);
@@ -279,12 +512,21 @@ impl TrackbearClient {
 	}
 
 	/// Validate the API key - checks token first, then service if token fails
+	///
+	/// A key which was validated recently isn't checked again.
 	pub async fn validate(&self) -> Result<()> {
+		if self.trackbear.is_validated(self.cache_key) {
+			return Ok(());
+		}
+
 		match self.ping_with_token().await {
-			Ok(()) => Ok(()),
+			Ok(()) => {
+				self.trackbear.set_validated(self.cache_key);
+				Ok(())
+			}
 			Err(err) => {
 				// If the token ping fails, check if the service is up
-				if Self::ping_service().await? {
+				if self.trackbear.ping_service().await? {
 					// Service is up, so the token is invalid
 					Err(err)
 				} else {
@@ -299,95 +541,216 @@ impl TrackbearClient {
 
 	/// List all projects
 	pub async fn list_projects(&self) -> Result<Vec<Project>> {
-		let url = format!("{}/project", API_BASE_URL);
-		debug!("fetching projects from TrackBear");
-
-		let response = self
-			.client
-			.get(&url)
-			.send()
-			.await
-			.into_diagnostic()
-			.wrap_err("failed to connect to TrackBear API")?;
-
-		let status = response.status();
-		debug!("list projects response: {}", status);
-
-		if !status.is_success() {
-			let error_text = response
-				.text()
-				.await
-				.unwrap_or_else(|_| "unknown error".to_string());
-			return Err(miette!(
-				"TrackBear API returned error (status {}): {}",
-				status,
-				error_text
-			));
+		if let Some(projects) = self.trackbear.cached(self.cache_key, |c| &mut c.projects) {
+			debug!("using cached projects");
+			return Ok(projects);
 		}
 
-		parse_api_response(response, "projects").await
+		let url = self.trackbear.url("project");
+		debug!("fetching projects from TrackBear");
+
+		let projects: Vec<Project> = self.call("list projects", |http| http.get(&url)).await?;
+		self.trackbear
+			.store(self.cache_key, |c| &mut c.projects, projects.clone());
+		Ok(projects)
 	}
 
 	/// List all goals
 	pub async fn list_goals(&self) -> Result<Vec<Goal>> {
-		let url = format!("{}/goal", API_BASE_URL);
+		if let Some(goals) = self.trackbear.cached(self.cache_key, |c| &mut c.goals) {
+			debug!("using cached goals");
+			return Ok(goals);
+		}
+
+		let url = self.trackbear.url("goal");
 		debug!("fetching goals from TrackBear");
 
-		let response = self
-			.client
-			.get(&url)
-			.send()
-			.await
-			.into_diagnostic()
-			.wrap_err("failed to connect to TrackBear API")?;
+		let goals: Vec<Goal> = self.call("list goals", |http| http.get(&url)).await?;
+		self.trackbear
+			.store(self.cache_key, |c| &mut c.goals, goals.clone());
+		Ok(goals)
+	}
 
-		let status = response.status();
-		debug!("list goals response: {}", status);
+	/// Create a new goal
+	pub async fn create_goal(&self, request: CreateGoalRequest) -> Result<Goal> {
+		let url = self.trackbear.url("goal");
+		debug!("creating goal on TrackBear: {:?}", request);
 
-		if !status.is_success() {
-			let error_text = response
-				.text()
-				.await
-				.unwrap_or_else(|_| "unknown error".to_string());
-			return Err(miette!(
-				"TrackBear API returned error (status {}): {}",
-				status,
-				error_text
-			));
-		}
+		let goal = self
+			.call("create goal", |http| http.post(&url).json(&request))
+			.await?;
+		self.trackbear.forget(self.cache_key, |c| &mut c.goals);
+		Ok(goal)
+	}
 
-		parse_api_response(response, "goals").await
+	/// Update a goal
+	pub async fn update_goal(&self, goal_id: i64, request: UpdateGoalRequest) -> Result<Goal> {
+		let url = self.trackbear.url(&format!("goal/{}", goal_id));
+		debug!("updating goal {} on TrackBear: {:?}", goal_id, request);
+
+		let goal = self
+			.call("update goal", |http| http.patch(&url).json(&request))
+			.await?;
+		self.trackbear.forget(self.cache_key, |c| &mut c.goals);
+		Ok(goal)
+	}
+
+	/// Delete a goal, which takes it out of the active goals
+	pub async fn delete_goal(&self, goal_id: i64) -> Result<Goal> {
+		let url = self.trackbear.url(&format!("goal/{}", goal_id));
+		debug!("deleting goal {} on TrackBear", goal_id);
+
+		let goal = self.call("delete goal", |http| http.delete(&url)).await?;
+		self.trackbear.forget(self.cache_key, |c| &mut c.goals);
+		Ok(goal)
 	}
 
 	/// Create a new tally
 	pub async fn create_tally(&self, request: CreateTallyRequest) -> Result<Tally> {
-		let url = format!("{}/tally", API_BASE_URL);
+		let url = self.trackbear.url("tally");
 		debug!("creating tally on TrackBear: {:?}", request);
 
-		let response = self
-			.client
-			.post(&url)
-			.json(&request)
-			.send()
-			.await
-			.into_diagnostic()
-			.wrap_err("failed to connect to TrackBear API")?;
+		let tally = self
+			.call("create tally", |http| http.post(&url).json(&request))
+			.await?;
+		self.forget_progress();
+		Ok(tally)
+	}
 
-		let status = response.status();
-		debug!("create tally response: {}", status);
+	/// List tallies, optionally only those for one project
+	pub async fn list_tallies(&self, project_id: Option<i64>) -> Result<Vec<Tally>> {
+		let url = self.trackbear.url("tally");
+		debug!(
+			"fetching tallies from TrackBear for project {:?}",
+			project_id
+		);
 
-		if !status.is_success() {
-			let error_text = response
-				.text()
-				.await
-				.unwrap_or_else(|_| "unknown error".to_string());
-			return Err(miette!(
-				"TrackBear API returned error (status {}): {}",
-				status,
-				error_text
-			));
-		}
+		self.call("tallies", |http| {
+			let request = http.get(&url);
+			if let Some(project_id) = project_id {
+				request.query(&[("works[]", project_id)])
+			} else {
+				request
+			}
+		})
+		.await
+	}
+
+	/// Update a tally
+	pub async fn update_tally(&self, tally_id: i64, request: UpdateTallyRequest) -> Result<Tally> {
+		let url = self.trackbear.url(&format!("tally/{}", tally_id));
+		debug!("updating tally {} on TrackBear: {:?}", tally_id, request);
+
+		let tally = self
+			.call("update tally", |http| http.patch(&url).json(&request))
+			.await?;
+		self.forget_progress();
+		Ok(tally)
+	}
+
+	/// Delete a tally
+	pub async fn delete_tally(&self, tally_id: i64) -> Result<Tally> {
+		let url = self.trackbear.url(&format!("tally/{}", tally_id));
+		debug!("deleting tally {} on TrackBear", tally_id);
+
+		let tally = self.call("delete tally", |http| http.delete(&url)).await?;
+		self.forget_progress();
+		Ok(tally)
+	}
+
+	/// Tallies change project totals and whether goals are achieved, so drop both from the cache
+	fn forget_progress(&self) {
+		self.trackbear.forget(self.cache_key, |c| &mut c.projects);
+		self.trackbear.forget(self.cache_key, |c| &mut c.goals);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	};
+
+	use tokio::{
+		io::{AsyncReadExt, AsyncWriteExt},
+		net::TcpListener,
+	};
+
+	use super::*;
+	use crate::config::TrackbearConfig;
+
+	/// Serve canned responses in order, one per connection, and count the requests served.
+	async fn mock_server(
+		responses: Vec<(&'static str, &'static str)>,
+	) -> (String, Arc<AtomicUsize>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let served = Arc::new(AtomicUsize::new(0));
+
+		let counter = served.clone();
+		tokio::spawn(async move {
+			for (status, body) in responses {
+				let (mut socket, _) = listener.accept().await.unwrap();
+				let mut request = [0; 4096];
+				let read = socket.read(&mut request).await.unwrap();
+				assert!(read > 0, "empty request");
+				counter.fetch_add(1, Ordering::SeqCst);
+
+				let response = format!(
+					"HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+					body.len()
+				);
+				socket.write_all(response.as_bytes()).await.unwrap();
+			}
+		});
+
+		(url, served)
+	}
+
+	#[tokio::test]
+	async fn retries_server_errors_then_caches() {
+		let (base_url, served) = mock_server(vec![
+			("503 Service Unavailable", "{}"),
+			("200 OK", r#"{"success":true,"data":[]}"#),
+		])
+		.await;
+		let trackbear = Trackbear::new(&TrackbearConfig {
+			base_url,
+			..Default::default()
+		})
+		.unwrap();
+		let client = trackbear.client(SecretValue::from("key"));
+
+		assert!(client.list_projects().await.unwrap().is_empty());
+		assert!(client.list_projects().await.unwrap().is_empty());
+		assert_eq!(served.load(Ordering::SeqCst), 2);
+	}
 
-		parse_api_response(response, "create tally").await
+	#[tokio::test]
+	async fn does_not_repeat_posts_after_server_errors() {
+		let (base_url, served) = mock_server(vec![
+			("503 Service Unavailable", "{}"),
+			("200 OK", r#"{"success":true,"data":{}}"#),
+		])
+		.await;
+		let trackbear = Trackbear::new(&TrackbearConfig {
+			base_url,
+			..Default::default()
+		})
+		.unwrap();
+		let client = trackbear.client(SecretValue::from("key"));
+
+		let tally = CreateTallyRequest {
+			date: "2024-11-01".into(),
+			measure: Measure::Word,
+			count: 500,
+			note: String::new(),
+			work_id: 1,
+			set_total: false,
+			tags: Vec::new(),
+		};
+		assert!(client.create_tally(tally).await.is_err());
+		assert_eq!(served.load(Ordering::SeqCst), 1);
 	}
 }
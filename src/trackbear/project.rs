@@ -2,7 +2,7 @@ use chrono::NaiveDate;
 use miette::{miette, Result};
 
 use super::client::{
-	CreateTallyRequest, Goal, Measure, Project as TbProject, Tally, TrackbearClient,
+	CreateTallyRequest, Goal, GoalType, Measure, Project as TbProject, Tally, TrackbearClient,
 };
 
 /// A TrackBear project with associated goals and tallies
@@ -49,25 +49,8 @@ impl Project {
 		let today = chrono::Utc::now().date_naive();
 
 		// Find goals that are currently active (between start and end date)
-		let mut active_goals: Vec<&Goal> = self
-			.goals
-			.iter()
-			.filter(|g| {
-				match (
-					g.start_date
-						.as_ref()
-						.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
-					g.end_date
-						.as_ref()
-						.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
-				) {
-					(Some(start), Some(end)) => today >= start && today <= end,
-					(Some(start), None) => today >= start,
-					(None, Some(end)) => today <= end,
-					(None, None) => false, // probably invalid
-				}
-			})
-			.collect();
+		let mut active_goals: Vec<&Goal> =
+			self.goals.iter().filter(|g| is_running(g, today)).collect();
 
 		// Sort by end date (prefer goals ending sooner)
 		active_goals.sort_by(|a, b| a.end_date.cmp(&b.end_date));
@@ -77,13 +60,14 @@ impl Project {
 
 	/// Calculate progress for a given goal
 	pub fn goal_progress(&self, goal: &Goal) -> Option<GoalProgress> {
-		// Only calculate for word-based goals
-		if goal.parameters.threshold.measure != Measure::Word {
+		// Only calculate for word-based target goals
+		let threshold = goal.parameters.threshold.as_ref()?;
+		if goal.goal_type != GoalType::Target || threshold.measure != Measure::Word {
 			return None;
 		}
 
 		let current = self.word_count();
-		let target = goal.parameters.threshold.count;
+		let target = threshold.count;
 
 		// Parse dates
 		let today = chrono::Utc::now().date_naive();
@@ -147,27 +131,118 @@ impl Project {
 		})
 	}
 
-	/// Create a tally for this project
-	pub async fn add_tally(
+	/// Get the most recent tallies for this project, newest first
+	pub async fn recent_tallies(
 		&self,
 		client: &TrackbearClient,
+		limit: usize,
+	) -> Result<Vec<Tally>> {
+		let mut tallies = client.list_tallies(Some(self.project.id)).await?;
+
+		// Sort by date, then by creation for tallies on the same day
+		tallies.sort_by(|a, b| (&b.date, &b.created_at).cmp(&(&a.date, &a.created_at)));
+		tallies.truncate(limit);
+
+		Ok(tallies)
+	}
+}
+
+/// Which day a tally is for and how it's annotated
+#[derive(Debug, Clone, Default)]
+pub struct TallyDetails {
+	/// Day the tally is for, or today in UTC
+	pub date: Option<NaiveDate>,
+	pub note: Option<String>,
+	/// Names of TrackBear tags
+	pub tags: Vec<String>,
+}
+
+impl TallyDetails {
+	/// The request to create a tally on a project with these details
+	pub fn into_request(
+		self,
+		project_id: i64,
+		measure: Measure,
 		count: i64,
 		set_total: bool,
-		note: Option<String>,
-	) -> Result<Tally> {
-		let date = chrono::Utc::now().date_naive();
+	) -> CreateTallyRequest {
+		let date = self.date.unwrap_or_else(|| chrono::Utc::now().date_naive());
 
-		let request = CreateTallyRequest {
+		CreateTallyRequest {
 			date: date.format("%Y-%m-%d").to_string(),
-			measure: Measure::Word,
+			measure,
 			count,
-			note: note.unwrap_or_default(),
-			work_id: self.project.id,
+			note: self.note.unwrap_or_default(),
+			work_id: project_id,
 			set_total,
-			tags: vec![],
-		};
+			tags: self.tags,
+		}
+	}
+}
 
-		client.create_tally(request).await
+/// Whether a goal is running on a day (between its start and end date)
+fn is_running(goal: &Goal, day: NaiveDate) -> bool {
+	match (
+		goal.start_date
+			.as_ref()
+			.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+		goal.end_date
+			.as_ref()
+			.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+	) {
+		(Some(start), Some(end)) => day >= start && day <= end,
+		(Some(start), None) => day >= start,
+		(None, Some(end)) => day <= end,
+		(None, None) => false, // probably invalid
+	}
+}
+
+/// A running goal, with the project it's for and its progress if it's a word count goal
+#[derive(Debug, Clone)]
+pub struct ActiveGoal {
+	pub goal: Goal,
+	pub project: Option<TbProject>,
+	pub progress: Option<GoalProgress>,
+}
+
+impl ActiveGoal {
+	/// Fetch all the goals which are running today, with their progress.
+	///
+	/// Progress is only measured for goals which are for a single project, as project totals
+	/// can't be split up to say how much of them counts for goals across several projects.
+	pub async fn fetch_all(client: &TrackbearClient) -> Result<Vec<Self>> {
+		let today = chrono::Utc::now().date_naive();
+		let projects = client.list_projects().await?;
+		let mut goals: Vec<Goal> = client
+			.list_goals()
+			.await?
+			.into_iter()
+			.filter(|g| g.state == "active" && is_running(g, today))
+			.collect();
+		goals.sort_by(|a, b| a.end_date.cmp(&b.end_date));
+
+		Ok(goals
+			.into_iter()
+			.map(|goal| {
+				let project = match goal.work_ids.as_slice() {
+					[id] => projects.iter().find(|p| p.id == *id).cloned(),
+					_ => None,
+				};
+				let progress = project.clone().and_then(|project| {
+					Project {
+						project,
+						goals: vec![goal.clone()],
+					}
+					.goal_progress(&goal)
+				});
+
+				Self {
+					goal,
+					project,
+					progress,
+				}
+			})
+			.collect())
 	}
 }
 
@@ -0,0 +1,244 @@
+use std::{
+	collections::{hash_map::DefaultHasher, HashMap},
+	hash::{Hash, Hasher},
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+use miette::{Context, IntoDiagnostic, Result};
+use reqwest::{header::HeaderMap, Client};
+use secret_vault_value::SecretValue;
+use tracing::{debug, warn};
+
+use super::client::{ApiError, Goal, Project, TrackbearClient};
+use crate::config::TrackbearConfig;
+
+/// How long a successful API key check is trusted for.
+const VALIDATION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Longest we'll wait out a rate limit within a request, rather than giving up.
+pub(super) const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+
+fn user_agent() -> String {
+	format!(
+		"{}/{} ({}) by {}",
+		env!("CARGO_PKG_NAME"),
+		env!("CARGO_PKG_VERSION"),
+		env!("CARGO_PKG_REPOSITORY"),
+		env!("CARGO_PKG_AUTHORS")
+	)
+}
+
+/// State shared by all TrackBear clients.
+///
+/// This holds the HTTP connection pool and the API base URL, when TrackBear last asked us to
+/// slow down, and short-lived caches of each login's projects and goals.
+#[derive(Debug, Clone)]
+pub struct Trackbear(Arc<Shared>);
+
+#[derive(Debug)]
+pub(super) struct Shared {
+	pub(super) http: Client,
+	pub(super) base_url: String,
+	pub(super) retries: u32,
+	cache_ttl: Duration,
+	state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+	/// Keyed by a hash of the API key, so there's one entry per member.
+	logins: HashMap<u64, LoginCache>,
+	/// All requests come from the bot's address, so a rate limit applies to every login.
+	rate_limited_until: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct LoginCache {
+	validated_at: Option<Instant>,
+	pub(super) projects: Option<(Instant, Vec<Project>)>,
+	pub(super) goals: Option<(Instant, Vec<Goal>)>,
+}
+
+impl LoginCache {
+	fn is_empty(&self) -> bool {
+		self.validated_at.is_none() && self.projects.is_none() && self.goals.is_none()
+	}
+}
+
+impl Trackbear {
+	pub fn new(config: &TrackbearConfig) -> Result<Self> {
+		let http = Client::builder()
+			.user_agent(user_agent())
+			.timeout(Duration::from_secs(config.timeout))
+			.build()
+			.into_diagnostic()
+			.wrap_err("failed to build HTTP client")?;
+
+		Ok(Self(Arc::new(Shared {
+			http,
+			base_url: config.base_url.trim_end_matches('/').to_string(),
+			retries: config.retries,
+			cache_ttl: Duration::from_secs(config.cache_ttl),
+			state: Default::default(),
+		})))
+	}
+
+	/// Get a client for an API key
+	pub fn client(&self, api_key: SecretValue) -> TrackbearClient {
+		let mut hasher = DefaultHasher::new();
+		api_key.as_sensitive_str().hash(&mut hasher);
+		TrackbearClient::new(self.clone(), api_key, hasher.finish())
+	}
+
+	/// Test API connectivity without authentication
+	pub async fn ping_service(&self) -> Result<bool> {
+		let url = self.url("ping");
+		debug!("pinging TrackBear service at {}", url);
+
+		match self.0.http.get(&url).send().await {
+			Ok(response) => {
+				let status = response.status();
+				debug!("service ping response: {}", status);
+				// Just check status code, no JSON response body
+				Ok(status.is_success())
+			}
+			Err(err) => {
+				warn!("service ping failed: {}", err);
+				Ok(false)
+			}
+		}
+	}
+
+	pub(super) fn shared(&self) -> &Shared {
+		&self.0
+	}
+
+	pub(super) fn url(&self, path: &str) -> String {
+		format!("{}/{}", self.0.base_url, path)
+	}
+
+	fn state(&self) -> std::sync::MutexGuard<'_, State> {
+		// it's only a cache, so it's fine to keep using after a panic
+		self.0.state.lock().unwrap_or_else(|err| err.into_inner())
+	}
+
+	/// Wait until TrackBear is willing to hear from us again, or error if that's too far off.
+	pub(super) async fn wait_for_rate_limit(&self) -> Result<()> {
+		let until = self.state().rate_limited_until;
+		let Some(wait) = until.and_then(|until| until.checked_duration_since(Instant::now()))
+		else {
+			return Ok(());
+		};
+
+		if wait > MAX_RATE_LIMIT_WAIT {
+			return Err(ApiError::RateLimited(wait).into());
+		}
+
+		debug!(?wait, "waiting out TrackBear rate limit");
+		tokio::time::sleep(wait).await;
+		Ok(())
+	}
+
+	pub(super) fn rate_limited_for(&self, wait: Duration) {
+		let until = Instant::now() + wait;
+		let mut state = self.state();
+		state.rate_limited_until = Some(state.rate_limited_until.map_or(until, |u| u.max(until)));
+	}
+
+	/// Back off ahead of time if the response says we've used up our requests.
+	pub(super) fn observe_rate_limit(&self, headers: &HeaderMap) {
+		if header_number(headers, "ratelimit-remaining") == Some(0) {
+			if let Some(reset) = header_number(headers, "ratelimit-reset") {
+				debug!(%reset, "TrackBear rate limit used up");
+				self.rate_limited_for(Duration::from_secs(reset));
+			}
+		}
+	}
+
+	pub(super) fn is_validated(&self, key: u64) -> bool {
+		self.state()
+			.logins
+			.get(&key)
+			.and_then(|login| login.validated_at)
+			.is_some_and(|at| at.elapsed() < VALIDATION_TTL)
+	}
+
+	pub(super) fn set_validated(&self, key: u64) {
+		self.with_login(key, |login| login.validated_at = Some(Instant::now()));
+	}
+
+	/// Get a cached value for the login, if it's fresh enough.
+	pub(super) fn cached<T: Clone>(
+		&self,
+		key: u64,
+		field: fn(&mut LoginCache) -> &mut Option<(Instant, T)>,
+	) -> Option<T> {
+		let ttl = self.0.cache_ttl;
+		self.state()
+			.logins
+			.get_mut(&key)
+			.and_then(|login| field(login).as_ref())
+			.filter(move |(at, _)| at.elapsed() < ttl)
+			.map(|(_, value)| value.clone())
+	}
+
+	pub(super) fn store<T>(
+		&self,
+		key: u64,
+		field: fn(&mut LoginCache) -> &mut Option<(Instant, T)>,
+		value: T,
+	) {
+		self.with_login(key, |login| *field(login) = Some((Instant::now(), value)));
+	}
+
+	pub(super) fn forget<T>(
+		&self,
+		key: u64,
+		field: fn(&mut LoginCache) -> &mut Option<(Instant, T)>,
+	) {
+		if let Some(login) = self.state().logins.get_mut(&key) {
+			*field(login) = None;
+		}
+	}
+
+	fn with_login(&self, key: u64, f: impl FnOnce(&mut LoginCache)) {
+		let ttl = self.0.cache_ttl;
+		let mut state = self.state();
+
+		// drop whatever went stale, so logins which aren't used anymore don't stick around
+		for login in state.logins.values_mut() {
+			if login
+				.validated_at
+				.is_some_and(|at| at.elapsed() >= VALIDATION_TTL)
+			{
+				login.validated_at = None;
+			}
+			if login
+				.projects
+				.as_ref()
+				.is_some_and(|(at, _)| at.elapsed() >= ttl)
+			{
+				login.projects = None;
+			}
+			if login
+				.goals
+				.as_ref()
+				.is_some_and(|(at, _)| at.elapsed() >= ttl)
+			{
+				login.goals = None;
+			}
+		}
+		state.logins.retain(|_, login| !login.is_empty());
+
+		f(state.logins.entry(key).or_default());
+	}
+}
+
+/// A header holding a whole number, like `Retry-After: 30`.
+pub(super) fn header_number(headers: &HeaderMap, name: &str) -> Option<u64> {
+	headers
+		.get(name)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.trim().parse().ok())
+}